@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::df;
+use polars::prelude::*;
+
+/// Join a large and a small frame on a key. The hash table of an inner join is built on the
+/// shorter input, so the order of the inputs should not matter.
+fn bench_join_order(c: &mut Criterion) {
+    let large = df![
+        "key" => (0..10_000_000).map(|i| i % 1000).collect::<Vec<i32>>(),
+        "value" => (0..10_000_000).collect::<Vec<i32>>()
+    ]
+    .unwrap();
+    let small = df![
+        "key" => (0..100).collect::<Vec<i32>>(),
+        "label" => (0..100).map(|i| i * 2).collect::<Vec<i32>>()
+    ]
+    .unwrap();
+
+    let mut group = c.benchmark_group("inner join 10M rows with 100 rows");
+    group.sample_size(10);
+    group.bench_function("large left", |b| {
+        b.iter(|| {
+            let q = large
+                .clone()
+                .lazy()
+                .inner_join(small.clone().lazy(), col("key"), col("key"));
+            criterion::black_box(q.collect().unwrap())
+        })
+    });
+    group.bench_function("large right", |b| {
+        b.iter(|| {
+            let q = small
+                .clone()
+                .lazy()
+                .inner_join(large.clone().lazy(), col("key"), col("key"));
+            criterion::black_box(q.collect().unwrap())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_join_order);
+criterion_main!(benches);
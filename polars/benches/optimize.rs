@@ -0,0 +1,109 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use polars::df;
+use polars::prelude::*;
+
+/// A plan with `n` filters, each followed by a projection.
+fn deep_plan(n: i32) -> LazyFrame {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => [1, 2, 3],
+        "c" => [1, 2, 3]
+    ]
+    .unwrap();
+
+    let mut q = df.lazy();
+    for i in 0..n {
+        q = q
+            .filter(col("a").gt(lit(i)))
+            .select([col("a"), col("b"), col("c")]);
+    }
+    q
+}
+
+fn bench_optimize(c: &mut Criterion) {
+    let q = deep_plan(1000);
+    c.bench_function("optimize 1000 projections and filters", |b| {
+        b.iter(|| criterion::black_box(q.describe_optimized_plan().unwrap()))
+    });
+}
+
+/// A filter and a projection of 10 columns of a frame with 1000 columns.
+fn wide_plan() -> LazyFrame {
+    let columns = (0..1000)
+        .map(|i| Series::new(&format!("c{}", i), [1i32, 2, 3]))
+        .collect::<Vec<_>>();
+    let df = DataFrame::new(columns).unwrap();
+
+    df.lazy()
+        .filter(col("c0").gt(lit(1)))
+        .select((0..10).map(|i| col(&format!("c{}", i))).collect::<Vec<_>>())
+}
+
+fn bench_optimize_wide_frame(c: &mut Criterion) {
+    let q = wide_plan();
+    c.bench_function("optimize a plan over 1000 columns", |b| {
+        b.iter(|| criterion::black_box(q.describe_optimized_plan().unwrap()))
+    });
+}
+
+/// An expensive string expression that is used three times in a projection.
+fn repeated_string_expr() -> LazyFrame {
+    let df = df![
+        "a" => (0..100_000).collect::<Vec<i32>>()
+    ]
+    .unwrap();
+
+    let s = || col("a").cast(DataType::Utf8) + lit("-suffix");
+    df.lazy().select([
+        s().alias("x"),
+        s().eq(lit("1-suffix")).alias("y"),
+        s().lt(lit("5")).alias("z"),
+    ])
+}
+
+fn bench_common_subexpr_elim(c: &mut Criterion) {
+    let q = repeated_string_expr();
+    let mut group = c.benchmark_group("repeated string expression");
+    group.bench_function("without cse", |b| {
+        b.iter(|| criterion::black_box(q.clone().collect().unwrap()))
+    });
+    group.bench_function("with cse", |b| {
+        b.iter(|| criterion::black_box(q.clone().with_common_subexpr_elim(true).collect().unwrap()))
+    });
+    group.finish();
+}
+
+/// An expensive string predicate stacked on a selective integer predicate that keeps 1% of the
+/// rows.
+fn selective_filters() -> LazyFrame {
+    let df = df![
+        "a" => (0..1_000_000).collect::<Vec<i32>>()
+    ]
+    .unwrap();
+
+    df.lazy()
+        .filter((col("a").cast(DataType::Utf8) + lit("-suffix")).neq(lit("1-suffix")))
+        .filter(col("a").lt(lit(10_000)))
+}
+
+fn bench_predicate_order(c: &mut Criterion) {
+    let q = selective_filters();
+    let mut group = c.benchmark_group("string predicate after selective predicate");
+    // without pushdown the filters are applied in the order they are stacked
+    group.bench_function("string predicate first", |b| {
+        b.iter(|| criterion::black_box(q.clone().with_predicate_pushdown(false).collect().unwrap()))
+    });
+    group.bench_function("ordered by cost", |b| {
+        b.iter(|| criterion::black_box(q.clone().collect().unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_optimize,
+    bench_optimize_wide_frame,
+    bench_common_subexpr_elim,
+    bench_predicate_order
+);
+criterion_main!(benches);
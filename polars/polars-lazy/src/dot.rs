@@ -131,7 +131,7 @@ impl LogicalPlan {
                 }
                 Ok(())
             }
-            Cache { input } => {
+            Cache { input, .. } => {
                 let current_node = format!("CACHE [{:?}]", (branch, id));
                 self.write_dot(acc_str, prev_node, &current_node, id)?;
                 input.dot(acc_str, (branch, id + 1), &current_node)
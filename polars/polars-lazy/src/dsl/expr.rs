@@ -2,6 +2,7 @@ use crate::prelude::*;
 use polars_core::prelude::*;
 use polars_core::utils::get_supertype;
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
 use crate::dsl::function_expr::FunctionExpr;
@@ -358,6 +359,24 @@ impl Default for Expr {
     }
 }
 
+/// Hashes the tree of expression kinds, the binary operators and the names of the columns and
+/// aliases. Equal expressions have all of these in common, so this is consistent with `PartialEq`.
+///
+/// `Expr` does not implement `Eq`: literal floats and functions are not equal to themselves.
+impl Hash for Expr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for e in self {
+            std::mem::discriminant(e).hash(state);
+            match e {
+                Expr::Column(name) | Expr::Alias(_, name) => name.hash(state),
+                Expr::Columns(names) => names.hash(state),
+                Expr::BinaryExpr { op, .. } => op.hash(state),
+                _ => {}
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Excluded {
     Name(Arc<str>),
@@ -374,7 +393,7 @@ impl Expr {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Operator {
     Eq,
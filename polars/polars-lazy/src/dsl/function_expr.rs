@@ -2,11 +2,19 @@ use super::*;
 use polars_core::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Debug)]
 pub enum FunctionExpr {
     NullCount,
+    /// Check if the strings contain a regex pattern.
+    #[cfg(feature = "strings")]
+    StringContains(String),
+    #[cfg(feature = "strings")]
+    StringStartsWith(String),
+    #[cfg(feature = "strings")]
+    StringEndsWith(String),
 }
 
 impl FunctionExpr {
@@ -19,6 +27,37 @@ impl FunctionExpr {
         use FunctionExpr::*;
         match self {
             NullCount => Ok(Field::new(fields[0].name(), IDX_DTYPE)),
+            #[cfg(feature = "strings")]
+            StringContains(_) | StringStartsWith(_) | StringEndsWith(_) => {
+                let field = &fields[0];
+                match field.data_type() {
+                    DataType::Utf8 => Ok(Field::new(field.name(), DataType::Boolean)),
+                    dt => Err(PolarsError::SchemaMisMatch(
+                        format!(
+                            "{} expects a Utf8 column, got column {:?} of type {:?}",
+                            self,
+                            field.name(),
+                            dt
+                        )
+                        .into(),
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for FunctionExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FunctionExpr::*;
+        match self {
+            NullCount => write!(f, "null_count()"),
+            #[cfg(feature = "strings")]
+            StringContains(pat) => write!(f, "str.contains({:?})", pat),
+            #[cfg(feature = "strings")]
+            StringStartsWith(prefix) => write!(f, "str.starts_with({:?})", prefix),
+            #[cfg(feature = "strings")]
+            StringEndsWith(suffix) => write!(f, "str.ends_with({:?})", suffix),
         }
     }
 }
@@ -40,6 +79,44 @@ impl From<FunctionExpr> for NoEq<Arc<dyn SeriesUdf>> {
                 };
                 wrap!(f)
             }
+            #[cfg(feature = "strings")]
+            StringContains(pat) => {
+                let f = move |s: &mut [Series]| {
+                    let ca = s[0].utf8()?;
+                    ca.contains(&pat).map(|ca| ca.into_series())
+                };
+                wrap!(f)
+            }
+            #[cfg(feature = "strings")]
+            StringStartsWith(prefix) => {
+                let f = move |s: &mut [Series]| {
+                    let ca = s[0].utf8()?;
+                    Ok(apply_str_predicate(ca, |s| s.starts_with(prefix.as_str())))
+                };
+                wrap!(f)
+            }
+            #[cfg(feature = "strings")]
+            StringEndsWith(suffix) => {
+                let f = move |s: &mut [Series]| {
+                    let ca = s[0].utf8()?;
+                    Ok(apply_str_predicate(ca, |s| s.ends_with(suffix.as_str())))
+                };
+                wrap!(f)
+            }
         }
     }
 }
+
+#[cfg(feature = "strings")]
+fn apply_str_predicate<F>(ca: &Utf8Chunked, f: F) -> Series
+where
+    F: Fn(&str) -> bool,
+{
+    let mut out: BooleanChunked = if !ca.has_validity() {
+        ca.into_no_null_iter().map(f).collect()
+    } else {
+        ca.into_iter().map(|opt_s| opt_s.map(&f)).collect()
+    };
+    out.rename(ca.name());
+    out.into_series()
+}
@@ -494,7 +494,7 @@ pub fn duration(args: DurationArgs) -> Expr {
     .alias("duration")
 }
 
-/// Concat multiple
+/// Concat multiple `LazyFrame`s vertically. All of them must have the same schema.
 pub fn concat<L: AsRef<[LazyFrame]>>(inputs: L, rechunk: bool) -> Result<LazyFrame> {
     let mut inputs = inputs.as_ref().to_vec();
     let lf = std::mem::take(
@@ -503,11 +503,22 @@ pub fn concat<L: AsRef<[LazyFrame]>>(inputs: L, rechunk: bool) -> Result<LazyFra
             .ok_or_else(|| PolarsError::ComputeError("empty container given".into()))?,
     );
     let opt_state = lf.opt_state;
+    let custom_optimizations = lf.custom_optimizations;
     let mut lps = Vec::with_capacity(inputs.len());
     lps.push(lf.logical_plan);
 
     for lf in &mut inputs[1..] {
         let lp = std::mem::take(&mut lf.logical_plan);
+        let (schema, first_schema) = (lp.schema(), lps[0].schema());
+        if schema != first_schema {
+            return Err(PolarsError::SchemaMisMatch(
+                format!(
+                    "cannot concat frames with different schemas: {:?} and {:?}",
+                    first_schema, schema
+                )
+                .into(),
+            ));
+        }
         lps.push(lp)
     }
 
@@ -517,6 +528,7 @@ pub fn concat<L: AsRef<[LazyFrame]>>(inputs: L, rechunk: bool) -> Result<LazyFra
     };
     let mut lf = LazyFrame::from(lp);
     lf.opt_state = opt_state;
+    lf.custom_optimizations = custom_optimizations;
 
     if rechunk {
         Ok(lf.map(
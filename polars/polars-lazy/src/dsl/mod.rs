@@ -697,6 +697,20 @@ impl Expr {
         }
     }
 
+    /// Apply a function that is element wise, so that it doesn't need to collect the groups.
+    fn map_private(self, function_expr: FunctionExpr, fmt_str: &'static str) -> Self {
+        Expr::Function {
+            input: vec![self],
+            function: function_expr,
+            options: FunctionOptions {
+                collect_groups: ApplyOptions::ApplyFlat,
+                input_wildcard_expansion: false,
+                auto_explode: false,
+                fmt_str,
+            },
+        }
+    }
+
     /// Apply a function/closure over the groups with many arguments. This should only be used in a groupby aggregation.
     ///
     /// See the [`Expr::apply`] function for the differences between [`map`](Expr::map) and [`apply`](Expr::apply).
@@ -1011,11 +1025,12 @@ impl Expr {
                 let a = &s[0];
                 let b = &s[1];
 
-                if !a.null_count() == 0 {
-                    Ok(a.clone())
+                // the output has the supertype, also if there is nothing to fill
+                let st = get_supertype(a.dtype(), b.dtype())?;
+                let a = a.cast(&st)?;
+                if a.null_count() == 0 {
+                    Ok(a)
                 } else {
-                    let st = get_supertype(a.dtype(), b.dtype())?;
-                    let a = a.cast(&st)?;
                     let b = b.cast(&st)?;
                     let mask = a.is_not_null();
                     a.zip_with_same_type(&mask, &b)
@@ -1027,6 +1042,16 @@ impl Expr {
         .with_fmt("fill_null")
     }
 
+    /// Replace the null values by a value computed with `strategy`, such as the previous value or
+    /// the mean of the column.
+    pub fn fill_null_with_strategy(self, strategy: FillNullStrategy) -> Self {
+        self.apply(
+            move |s: Series| s.fill_null(strategy),
+            GetOutput::same_type(),
+        )
+        .with_fmt("fill_null_with_strategy")
+    }
+
     /// Replace the floating point `NaN` values by a value.
     pub fn fill_nan(self, fill_value: Expr) -> Self {
         when(self.clone().is_nan()).then(fill_value).otherwise(self)
@@ -7,6 +7,30 @@ use polars_time::prelude::*;
 pub struct StringNameSpace(pub(crate) Expr);
 
 impl StringNameSpace {
+    /// Check if the strings contain a regex pattern.
+    pub fn contains(self, pat: &str) -> Expr {
+        self.0.map_private(
+            FunctionExpr::StringContains(pat.to_string()),
+            "str.contains",
+        )
+    }
+
+    /// Check if the strings start with a prefix.
+    pub fn starts_with(self, prefix: &str) -> Expr {
+        self.0.map_private(
+            FunctionExpr::StringStartsWith(prefix.to_string()),
+            "str.starts_with",
+        )
+    }
+
+    /// Check if the strings end with a suffix.
+    pub fn ends_with(self, suffix: &str) -> Expr {
+        self.0.map_private(
+            FunctionExpr::StringEndsWith(suffix.to_string()),
+            "str.ends_with",
+        )
+    }
+
     pub fn extract(self, pat: &str, group_index: usize) -> Expr {
         let pat = pat.to_string();
         let function = move |s: Series| {
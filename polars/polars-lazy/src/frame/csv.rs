@@ -4,11 +4,13 @@ use polars_io::csv::{CsvEncoding, NullValues};
 use polars_io::csv_core::utils::get_reader_bytes;
 use polars_io::csv_core::utils::infer_file_schema;
 use polars_io::RowCount;
+use std::path::PathBuf;
 
 #[derive(Clone)]
 #[cfg(feature = "csv-file")]
 pub struct LazyCsvReader<'a> {
     path: String,
+    paths: Vec<PathBuf>,
     delimiter: u8,
     has_header: bool,
     ignore_errors: bool,
@@ -34,6 +36,7 @@ impl<'a> LazyCsvReader<'a> {
     pub fn new(path: String) -> Self {
         LazyCsvReader {
             path,
+            paths: vec![],
             delimiter: b',',
             has_header: true,
             ignore_errors: false,
@@ -55,6 +58,13 @@ impl<'a> LazyCsvReader<'a> {
         }
     }
 
+    /// Scan multiple csv files as a single `LazyFrame`. The files must have the same schema.
+    pub fn new_paths(paths: Vec<PathBuf>) -> Self {
+        let mut reader = Self::new(String::new());
+        reader.paths = paths;
+        reader
+    }
+
     /// Skip this number of rows after the header location.
     #[must_use]
     pub fn with_skip_rows_after_header(mut self, offset: usize) -> Self {
@@ -188,11 +198,17 @@ impl<'a> LazyCsvReader<'a> {
     /// Modify a schema before we run the lazy scanning.
     ///
     /// Important! Run this function latest in the builder!
+    ///
+    /// The schema of multiple files is inferred from the first file.
     pub fn with_schema_modify<F>(self, f: F) -> Result<Self>
     where
         F: Fn(Schema) -> Result<Schema>,
     {
-        let mut file = std::fs::File::open(&self.path)?;
+        let path = match self.paths.first() {
+            Some(path) => path.clone(),
+            None => PathBuf::from(&self.path),
+        };
+        let mut file = std::fs::File::open(&path)?;
         let reader_bytes = get_reader_bytes(&mut file).expect("could not mmap file");
         let mut skip_rows = self.skip_rows;
 
@@ -249,35 +265,65 @@ impl<'a> LazyCsvReader<'a> {
         Ok(lf)
     }
 
+    /// Scan the files at `paths` and concatenate them.
+    fn finish_paths<I>(self, paths: I) -> Result<LazyFrame>
+    where
+        I: Iterator<Item = Result<PathBuf>>,
+    {
+        // (path, schema) of the first file
+        let mut first: Option<(String, SchemaRef)> = None;
+        let mut lfs = vec![];
+        for path in paths {
+            let path_string = path?.to_string_lossy().into_owned();
+            let mut builder = self.clone();
+            builder.path = path_string.clone();
+            builder.paths = vec![];
+            if builder.skip_rows > 0 {
+                builder.skip_rows = 0;
+                builder.n_rows = None;
+            }
+            // do no rechunk yet.
+            builder.rechunk = false;
+            let lf = builder.finish_impl()?;
+
+            let schema = lf.schema();
+            match &first {
+                Some((first_path, first_schema)) if first_schema != &schema => {
+                    return Err(PolarsError::SchemaMisMatch(
+                        format!(
+                            "the schema of {:?}: {:?} does not match the schema of {:?}: {:?}",
+                            path_string, schema, first_path, first_schema
+                        )
+                        .into(),
+                    ));
+                }
+                Some(_) => {}
+                None => first = Some((path_string, schema)),
+            }
+            lfs.push(lf);
+        }
+        if lfs.is_empty() {
+            return Err(PolarsError::ComputeError("no matching files found".into()));
+        }
+        concat(&lfs, self.rechunk).map(|lf| {
+            if self.skip_rows != 0 || self.n_rows.is_some() {
+                let n_rows = self.n_rows.map(|n| n as IdxSize).unwrap_or(IdxSize::MAX);
+                lf.slice(self.skip_rows as i64, n_rows)
+            } else {
+                lf
+            }
+        })
+    }
+
     pub fn finish(self) -> Result<LazyFrame> {
-        if self.path.contains('*') {
+        if !self.paths.is_empty() {
+            let paths = self.paths.clone();
+            self.finish_paths(paths.into_iter().map(Ok))
+        } else if self.path.contains('*') {
             let paths = glob::glob(&self.path)
-                .map_err(|_| PolarsError::ComputeError("invalid glob pattern given".into()))?;
-
-            let lfs = paths
-                .map(|r| {
-                    let path = r.map_err(|e| PolarsError::ComputeError(format!("{}", e).into()))?;
-                    let path_string = path.to_string_lossy().into_owned();
-                    let mut builder = self.clone();
-                    builder.path = path_string;
-                    if builder.skip_rows > 0 {
-                        builder.skip_rows = 0;
-                        builder.n_rows = None;
-                    }
-                    // do no rechunk yet.
-                    builder.rechunk = false;
-                    builder.finish_impl()
-                })
-                .collect::<Result<Vec<_>>>()?;
-            concat(&lfs, self.rechunk)
-                .map_err(|_| PolarsError::ComputeError("no matching files found".into()))
-                .map(|lf| {
-                    if self.skip_rows != 0 || self.n_rows.is_some() {
-                        lf.slice(self.skip_rows as i64, self.n_rows.unwrap() as IdxSize)
-                    } else {
-                        lf
-                    }
-                })
+                .map_err(|_| PolarsError::ComputeError("invalid glob pattern given".into()))?
+                .map(|r| r.map_err(|e| PolarsError::ComputeError(format!("{}", e).into())));
+            self.finish_paths(paths)
         } else {
             self.finish_impl()
         }
@@ -37,8 +37,11 @@ use serde::{Deserialize, Serialize};
 #[cfg(any(feature = "parquet", feature = "csv-file"))]
 use crate::prelude::aggregate_scan_projections::agg_projection;
 use crate::prelude::{
+    combine_filters::CombineFilters, common_subplan::CommonSubplanElim, cse::CommonSubExprElim,
     drop_nulls::ReplaceDropNulls, fast_projection::FastProjection,
-    simplify_expr::SimplifyBooleanRule, slice_pushdown_lp::SlicePushDown, *,
+    identity_projection::RemoveIdentityProjection, merge_projections::MergeProjections,
+    redundant_sort::RemoveRedundantSort, simplify_expr::SimplifyBooleanRule,
+    slice_pushdown_lp::SlicePushDown, *,
 };
 
 use crate::logical_plan::FETCH_ROWS;
@@ -69,6 +72,17 @@ impl Default for JoinOptions {
     }
 }
 
+impl JoinOptions {
+    /// Semi and anti joins only filter the left table, they don't add the columns of the right table.
+    pub(crate) fn is_semi_anti(&self) -> bool {
+        match self.how {
+            #[cfg(feature = "semi_anti_join")]
+            JoinType::Semi | JoinType::Anti => true,
+            _ => false,
+        }
+    }
+}
+
 pub trait IntoLazy {
     fn lazy(self) -> LazyFrame;
 }
@@ -88,6 +102,8 @@ impl IntoLazy for DataFrame {
 pub struct LazyFrame {
     pub logical_plan: LogicalPlan,
     pub(crate) opt_state: OptState,
+    /// User defined optimizations that run after the built-in optimizations
+    pub(crate) custom_optimizations: CustomOptimizations,
 }
 
 impl From<LogicalPlan> for LazyFrame {
@@ -95,10 +111,13 @@ impl From<LogicalPlan> for LazyFrame {
         Self {
             logical_plan: plan,
             opt_state: Default::default(),
+            custom_optimizations: vec![],
         }
     }
 }
 
+pub(crate) type CustomOptimizations = Vec<Arc<dyn Optimize + Send + Sync>>;
+
 #[derive(Copy, Clone)]
 /// State of the allowed optimizations
 pub struct OptState {
@@ -111,6 +130,8 @@ pub struct OptState {
     pub aggregate_pushdown: bool,
     pub global_string_cache: bool,
     pub slice_pushdown: bool,
+    pub common_subexpr_elim: bool,
+    pub common_subplan_elim: bool,
 }
 
 impl Default for OptState {
@@ -125,6 +146,8 @@ impl Default for OptState {
             // will be toggled by a scan operation such as csv scan or parquet scan
             agg_scan_projection: false,
             aggregate_pushdown: false,
+            common_subexpr_elim: false,
+            common_subplan_elim: true,
         }
     }
 }
@@ -132,6 +155,30 @@ impl Default for OptState {
 /// AllowedOptimizations
 pub type AllowedOptimizations = OptState;
 
+/// The maximum number of times the projection and predicate pushdown are run.
+const MAX_PUSHDOWN_PASSES: usize = 4;
+
+/// Describe the plan at `lp_top` without taking it out of the arenas.
+fn describe_alp(lp_top: Node, lp_arena: &Arena<ALogicalPlan>, expr_arena: &Arena<AExpr>) -> String {
+    // converting to a `LogicalPlan` takes the nodes out of the arenas, so convert a copy
+    let mut lp_arena = lp_arena.clone();
+    let mut expr_arena = expr_arena.clone();
+    node_to_lp(lp_top, &mut expr_arena, &mut lp_arena).describe()
+}
+
+/// Push the description of the plan at `lp_top` after the optimization pass `name` to `trace`.
+fn trace_pass(
+    trace: &mut Option<&mut Vec<(String, String)>>,
+    name: &str,
+    lp_top: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) {
+    if let Some(trace) = trace {
+        trace.push((name.to_string(), describe_alp(lp_top, lp_arena, expr_arena)));
+    }
+}
+
 impl LazyFrame {
     /// Get a hold on the schema of the current LazyFrame computation.
     pub fn schema(&self) -> SchemaRef {
@@ -147,10 +194,15 @@ impl LazyFrame {
         self.opt_state
     }
 
-    fn from_logical_plan(logical_plan: LogicalPlan, opt_state: OptState) -> Self {
+    fn from_logical_plan(
+        logical_plan: LogicalPlan,
+        opt_state: OptState,
+        custom_optimizations: CustomOptimizations,
+    ) -> Self {
         LazyFrame {
             logical_plan,
             opt_state,
+            custom_optimizations,
         }
     }
 
@@ -192,6 +244,25 @@ impl LazyFrame {
         self
     }
 
+    /// Toggle common subexpression elimination.
+    pub fn with_common_subexpr_elim(mut self, toggle: bool) -> Self {
+        self.opt_state.common_subexpr_elim = toggle;
+        self
+    }
+
+    /// Toggle caching of subplans that occur in multiple inputs of a join or union.
+    pub fn with_common_subplan_elim(mut self, toggle: bool) -> Self {
+        self.opt_state.common_subplan_elim = toggle;
+        self
+    }
+
+    /// Add an optimization that runs on the logical plan after the built-in optimizations.
+    /// Optimizations run in the order in which they are added.
+    pub fn with_optimization(mut self, optimization: Box<dyn Optimize + Send + Sync>) -> Self {
+        self.custom_optimizations.push(Arc::from(optimization));
+        self
+    }
+
     /// Toggle global string cache.
     pub fn with_string_cache(mut self, toggle: bool) -> Self {
         self.opt_state.global_string_cache = toggle;
@@ -218,6 +289,28 @@ impl LazyFrame {
         Ok(logical_plan.describe())
     }
 
+    /// Describe the logical plan, optimized or as it is built.
+    pub fn explain(&self, optimized: bool) -> Result<String> {
+        if optimized {
+            self.describe_optimized_plan()
+        } else {
+            Ok(self.describe_plan())
+        }
+    }
+
+    /// Optimize the logical plan and describe it after every optimization pass that runs.
+    ///
+    /// Returns the name of each pass with the description of the plan after it, in the order the
+    /// passes run. The description after the last pass is the optimized plan.
+    pub fn trace_optimizations(&self) -> Result<Vec<(String, String)>> {
+        let mut expr_arena = Arena::with_capacity(512);
+        let mut lp_arena = Arena::with_capacity(512);
+        let mut trace = vec![];
+        self.clone()
+            .optimize_with_trace(&mut lp_arena, &mut expr_arena, Some(&mut trace))?;
+        Ok(trace)
+    }
+
     /// Add a sort operation to the logical plan.
     ///
     /// # Example
@@ -237,11 +330,12 @@ impl LazyFrame {
         let nulls_last = options.nulls_last;
 
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self
             .get_plan_builder()
-            .sort(vec![col(by_column)], vec![reverse], nulls_last)
+            .sort(vec![col(by_column)], vec![reverse], vec![nulls_last])
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Add a sort operation to the logical plan.
@@ -259,16 +353,45 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn sort_by_exprs<E: AsRef<[Expr]>>(self, by_exprs: E, reverse: Vec<bool>) -> Self {
+        // nulls are placed first in an ascending order and last in a descending order
+        let nulls_last = reverse.clone();
+        self.sort_by_exprs_with_nulls_last(by_exprs, reverse, nulls_last)
+    }
+
+    /// Add a sort operation to the logical plan, with the placement of the nulls of each sort key.
+    ///
+    /// The nulls of a key are placed before or after its other values, regardless of the order
+    /// of the key. Like `reverse`, `nulls_last` may have a single value that is used for all keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// /// Sort DataFrame by 'sepal.width' descending, with the nulls first
+    /// fn example(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .sort_by_exprs_with_nulls_last(vec![col("sepal.width")], vec![true], vec![false])
+    /// }
+    /// ```
+    pub fn sort_by_exprs_with_nulls_last<E: AsRef<[Expr]>>(
+        self,
+        by_exprs: E,
+        reverse: Vec<bool>,
+        nulls_last: Vec<bool>,
+    ) -> Self {
         let by_exprs = by_exprs.as_ref().to_vec();
         if by_exprs.is_empty() {
             self
         } else {
             let opt_state = self.get_opt_state();
+            let custom_optimizations = self.custom_optimizations.clone();
             let lp = self
                 .get_plan_builder()
-                .sort(by_exprs, reverse, false)
+                .sort(by_exprs, reverse, nulls_last)
                 .build();
-            Self::from_logical_plan(lp, opt_state)
+            Self::from_logical_plan(lp, opt_state, custom_optimizations)
         }
     }
 
@@ -291,11 +414,15 @@ impl LazyFrame {
 
     fn rename_impl_swapping(self, existing: Vec<String>, new: Vec<String>) -> Self {
         // schema after renaming
-        let mut new_schema = (&*self.schema()).clone();
-
-        for (old, new) in existing.iter().zip(new.iter()) {
-            new_schema.rename(old, new.to_string()).unwrap();
-        }
+        // the names are mapped in place, because renaming one by one would overwrite the swapped
+        // columns
+        let new_schema = Schema::from(self.schema().iter().map(|(name, dtype)| {
+            let name = match existing.iter().position(|old| old == name) {
+                Some(idx) => new[idx].clone(),
+                None => name.clone(),
+            };
+            Field::new(&name, dtype.clone())
+        }));
 
         let prefix = "__POLARS_TEMP_";
 
@@ -337,7 +464,12 @@ impl LazyFrame {
                 cols.truncate(cols.len() - existing.len());
                 DataFrame::new(cols)
             },
-            None,
+            // the names of the predicates above refer to the swapped columns,
+            // they cannot be pushed down with the same name.
+            Some(AllowedOptimizations {
+                predicate_pushdown: false,
+                ..Default::default()
+            }),
             Some(new_schema),
             Some("RENAME_SWAPPING"),
         )
@@ -423,7 +555,10 @@ impl LazyFrame {
 
     #[allow(clippy::ptr_arg)]
     fn drop_columns_impl(self, columns: &Vec<String>) -> Self {
-        self.select_local(vec![col("*").exclude(columns)])
+        let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
+        let lp = self.get_plan_builder().drop_columns(columns).build();
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Shift the values by a given period and fill the parts that will be empty due to this operation
@@ -445,23 +580,26 @@ impl LazyFrame {
     /// Fill none values in the DataFrame
     pub fn fill_null<E: Into<Expr>>(self, fill_value: E) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().fill_null(fill_value.into()).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Fill NaN values in the DataFrame
     pub fn fill_nan<E: Into<Expr>>(self, fill_value: E) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().fill_nan(fill_value.into()).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Caches the result into a new LazyFrame. This should be used to prevent computations
     /// running multiple times
     pub fn cache(self) -> Self {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().cache().build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Fetch is like a collect operation, but it overwrites the number of rows read by every scan
@@ -481,6 +619,17 @@ impl LazyFrame {
         self,
         lp_arena: &mut Arena<ALogicalPlan>,
         expr_arena: &mut Arena<AExpr>,
+    ) -> Result<Node> {
+        self.optimize_with_trace(lp_arena, expr_arena, None)
+    }
+
+    /// Optimize the logical plan. If a `trace` is given, the name of every optimization pass
+    /// that runs and the description of the plan after that pass are pushed to it.
+    fn optimize_with_trace(
+        self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        mut trace: Option<&mut Vec<(String, String)>>,
     ) -> Result<Node> {
         // get toggle values
         let predicate_pushdown = self.opt_state.predicate_pushdown;
@@ -492,6 +641,9 @@ impl LazyFrame {
         #[cfg(any(feature = "parquet", feature = "csv-file"))]
         let agg_scan_projection = self.opt_state.agg_scan_projection;
         let aggregate_pushdown = self.opt_state.aggregate_pushdown;
+        let common_subexpr_elim = self.opt_state.common_subexpr_elim;
+        let common_subplan_elim = self.opt_state.common_subplan_elim;
+        let custom_optimizations = self.custom_optimizations.clone();
 
         let logical_plan = self.get_plan_builder().build();
 
@@ -510,39 +662,87 @@ impl LazyFrame {
 
         let mut lp_top = to_alp(logical_plan, expr_arena, lp_arena)?;
 
+        let mut pushdown_rules: Vec<Box<dyn OptimizationRule>> = vec![
+            Box::new(CombineFilters {}),
+            Box::new(MergeProjections {}),
+            Box::new(RemoveIdentityProjection {}),
+        ];
+
         // simplify expression is valuable for projection and predicate pushdown optimizers, so we
         // run that first
         // this optimization will run twice because optimizer may create dumb expressions
         lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top);
-
-        if projection_pushdown {
-            let projection_pushdown_opt = ProjectionPushDown {};
-            let alp = lp_arena.take(lp_top);
-            let alp = projection_pushdown_opt
-                .optimize(alp, lp_arena, expr_arena)
-                .expect("projection pushdown failed");
-            lp_arena.replace(lp_top, alp);
+        if simplify_expr {
+            trace_pass(&mut trace, "simplify_expr", lp_top, lp_arena, expr_arena);
         }
 
-        if predicate_pushdown {
-            let predicate_pushdown_opt = PredicatePushDown::default();
-            let alp = lp_arena.take(lp_top);
-            let alp = predicate_pushdown_opt
-                .optimize(alp, lp_arena, expr_arena)
-                .expect("predicate pushdown failed");
-            lp_arena.replace(lp_top, alp);
+        // the pushdowns can create opportunities for each other, e.g. a predicate that is
+        // pushed below a join changes the columns the join needs. So they are repeated until the
+        // plan doesn't change anymore.
+        if projection_pushdown || predicate_pushdown {
+            let mut description = describe_alp(lp_top, lp_arena, expr_arena);
+            for _ in 0..MAX_PUSHDOWN_PASSES {
+                if projection_pushdown {
+                    let projection_pushdown_opt = ProjectionPushDown {};
+                    let alp = lp_arena.take(lp_top);
+                    let alp = projection_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
+                    lp_arena.replace(lp_top, alp);
+                    trace_pass(
+                        &mut trace,
+                        "projection_pushdown",
+                        lp_top,
+                        lp_arena,
+                        expr_arena,
+                    );
+                }
+
+                if predicate_pushdown {
+                    let predicate_pushdown_opt = PredicatePushDown::default();
+                    let alp = lp_arena.take(lp_top);
+                    let alp = predicate_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
+                    lp_arena.replace(lp_top, alp);
+                    trace_pass(
+                        &mut trace,
+                        "predicate_pushdown",
+                        lp_top,
+                        lp_arena,
+                        expr_arena,
+                    );
+                }
+
+                // clean up the projections and filters that a pass leaves behind, so that a next
+                // pass that doesn't move anything gives the same plan.
+                lp_top = opt.optimize_loop(&mut pushdown_rules, expr_arena, lp_arena, lp_top);
+
+                let new_description = describe_alp(lp_top, lp_arena, expr_arena);
+                if new_description == description {
+                    break;
+                }
+                description = new_description;
+            }
         }
+        rules.push(Box::new(CombineFilters {}));
+        // merge the projections before they are turned into fast projections.
+        rules.push(Box::new(MergeProjections {}));
+        rules.push(Box::new(RemoveIdentityProjection {}));
+        rules.push(Box::new(RemoveRedundantSort {}));
         // make sure its before slice pushdown.
         rules.push(Box::new(FastProjection {}));
 
+        if common_subexpr_elim {
+            rules.push(Box::new(CommonSubExprElim {}));
+        }
+        if common_subplan_elim {
+            rules.push(Box::new(CommonSubplanElim {}));
+        }
+
         if slice_pushdown {
             let slice_pushdown_opt = SlicePushDown {};
             let alp = lp_arena.take(lp_top);
-            let alp = slice_pushdown_opt
-                .optimize(alp, lp_arena, expr_arena)
-                .expect("slice pushdown failed");
+            let alp = slice_pushdown_opt.optimize(alp, lp_arena, expr_arena)?;
 
             lp_arena.replace(lp_top, alp);
+            trace_pass(&mut trace, "slice_pushdown", lp_top, lp_arena, expr_arena);
 
             // expressions use the stack optimizer
             rules.push(Box::new(slice_pushdown_opt));
@@ -570,6 +770,24 @@ impl LazyFrame {
         rules.push(Box::new(ReplaceDropNulls {}));
 
         lp_top = opt.optimize_loop(&mut rules, expr_arena, lp_arena, lp_top);
+        trace_pass(
+            &mut trace,
+            "optimization_rules",
+            lp_top,
+            lp_arena,
+            expr_arena,
+        );
+
+        if !custom_optimizations.is_empty() {
+            let mut logical_plan = node_to_lp(lp_top, expr_arena, lp_arena);
+            for optimization in &custom_optimizations {
+                logical_plan = optimization.optimize(logical_plan)?;
+                if let Some(trace) = trace.as_mut() {
+                    trace.push(("custom_optimization".to_string(), logical_plan.describe()));
+                }
+            }
+            lp_top = to_alp(logical_plan, expr_arena, lp_arena)?;
+        }
 
         // during debug we check if the optimizations have not modified the final schema
         #[cfg(debug_assertions)]
@@ -649,8 +867,9 @@ impl LazyFrame {
     /// ```
     pub fn filter(self, predicate: Expr) -> Self {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().filter(predicate).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Select (and rename) columns from the query.
@@ -680,19 +899,21 @@ impl LazyFrame {
     /// ```
     pub fn select<E: AsRef<[Expr]>>(self, exprs: E) -> Self {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self
             .get_plan_builder()
             .project(exprs.as_ref().to_vec())
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// A projection that doesn't get optimized and may drop projections if they are not in
     /// schema after optimization
     fn select_local(self, exprs: Vec<Expr>) -> Self {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().project_local(exprs).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Group by and aggregate.
@@ -716,9 +937,11 @@ impl LazyFrame {
     /// ```
     pub fn groupby<E: AsRef<[Expr]>>(self, by: E) -> LazyGroupBy {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         LazyGroupBy {
             logical_plan: self.logical_plan,
             opt_state,
+            custom_optimizations,
             keys: by.as_ref().to_vec(),
             maintain_order: false,
             dynamic_options: None,
@@ -732,9 +955,11 @@ impl LazyFrame {
         options: RollingGroupOptions,
     ) -> LazyGroupBy {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         LazyGroupBy {
             logical_plan: self.logical_plan,
             opt_state,
+            custom_optimizations,
             keys: by.as_ref().to_vec(),
             maintain_order: true,
             dynamic_options: None,
@@ -748,9 +973,11 @@ impl LazyFrame {
         options: DynamicGroupOptions,
     ) -> LazyGroupBy {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         LazyGroupBy {
             logical_plan: self.logical_plan,
             opt_state,
+            custom_optimizations,
             keys: by.as_ref().to_vec(),
             maintain_order: true,
             dynamic_options: Some(options),
@@ -761,9 +988,11 @@ impl LazyFrame {
     /// Similar to groupby, but order of the DataFrame is maintained.
     pub fn groupby_stable<E: AsRef<[Expr]>>(self, by: E) -> LazyGroupBy {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         LazyGroupBy {
             logical_plan: self.logical_plan,
             opt_state,
+            custom_optimizations,
             keys: by.as_ref().to_vec(),
             maintain_order: true,
             dynamic_options: None,
@@ -879,8 +1108,9 @@ impl LazyFrame {
     /// ```
     pub fn with_column(self, expr: Expr) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().with_columns(vec![expr]).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Add multiple columns to a DataFrame.
@@ -900,8 +1130,9 @@ impl LazyFrame {
     pub fn with_columns<E: AsRef<[Expr]>>(self, exprs: E) -> LazyFrame {
         let exprs = exprs.as_ref().to_vec();
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().with_columns(exprs).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Aggregate all the columns as their maximum values.
@@ -914,6 +1145,13 @@ impl LazyFrame {
         self.select_local(vec![col("*").min()])
     }
 
+    /// Count the rows. The result has a single `count` column.
+    ///
+    /// Only the cheapest column of the input is read to count the rows.
+    pub fn count(self) -> LazyFrame {
+        self.select([count()])
+    }
+
     /// Aggregate all the columns as their sum values.
     pub fn sum(self) -> LazyFrame {
         self.select_local(vec![col("*").sum()])
@@ -948,8 +1186,9 @@ impl LazyFrame {
     pub fn explode<E: AsRef<[Expr]>>(self, columns: E) -> LazyFrame {
         let columns = columns.as_ref().to_vec();
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().explode(columns).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Keep unique rows and maintain order
@@ -959,13 +1198,14 @@ impl LazyFrame {
         keep_strategy: UniqueKeepStrategy,
     ) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let options = DistinctOptions {
             subset: subset.map(Arc::new),
             maintain_order: true,
             keep_strategy,
         };
         let lp = self.get_plan_builder().distinct(options).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Keep unique rows, do not maintain order
@@ -975,13 +1215,14 @@ impl LazyFrame {
         keep_strategy: UniqueKeepStrategy,
     ) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let options = DistinctOptions {
             subset: subset.map(Arc::new),
             maintain_order: false,
             keep_strategy,
         };
         let lp = self.get_plan_builder().distinct(options).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Drop null rows.
@@ -1001,8 +1242,9 @@ impl LazyFrame {
     /// Slice the DataFrame.
     pub fn slice(self, offset: i64, len: IdxSize) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().slice(offset, len).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Get the first row.
@@ -1024,8 +1266,9 @@ impl LazyFrame {
     /// Melt the DataFrame from wide to long format
     pub fn melt(self, args: MeltArgs) -> LazyFrame {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self.get_plan_builder().melt(Arc::new(args)).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Limit the DataFrame to the first `n` rows. Note if you don't want the rows to be scanned,
@@ -1052,6 +1295,7 @@ impl LazyFrame {
         F: 'static + Fn(DataFrame) -> Result<DataFrame> + Send + Sync,
     {
         let opt_state = self.get_opt_state();
+        let custom_optimizations = self.custom_optimizations.clone();
         let lp = self
             .get_plan_builder()
             .map(
@@ -1061,7 +1305,7 @@ impl LazyFrame {
                 name.unwrap_or("ANONYMOUS UDF"),
             )
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 
     /// Add a new column at index 0 that counts the rows.
@@ -1160,6 +1404,7 @@ impl LazyFrame {
 pub struct LazyGroupBy {
     pub(crate) logical_plan: LogicalPlan,
     opt_state: OptState,
+    custom_optimizations: CustomOptimizations,
     keys: Vec<Expr>,
     maintain_order: bool,
     dynamic_options: Option<DynamicGroupOptions>,
@@ -1200,7 +1445,7 @@ impl LazyGroupBy {
                 self.rolling_options,
             )
             .build();
-        LazyFrame::from_logical_plan(lp, self.opt_state)
+        LazyFrame::from_logical_plan(lp, self.opt_state, self.custom_optimizations)
     }
 
     /// Return first n rows of each group
@@ -1243,7 +1488,7 @@ impl LazyGroupBy {
                 None,
             )
             .build();
-        LazyFrame::from_logical_plan(lp, self.opt_state)
+        LazyFrame::from_logical_plan(lp, self.opt_state, self.custom_optimizations)
     }
 }
 
@@ -1316,7 +1561,8 @@ impl JoinBuilder {
 
     /// Finish builder
     pub fn finish(self) -> LazyFrame {
-        let opt_state = self.lf.opt_state;
+        let opt_state = self.lf.get_opt_state();
+        let custom_optimizations = self.lf.custom_optimizations.clone();
 
         let suffix = match self.suffix {
             None => Cow::Borrowed("_right"),
@@ -1339,6 +1585,6 @@ impl JoinBuilder {
                 },
             )
             .build();
-        LazyFrame::from_logical_plan(lp, opt_state)
+        LazyFrame::from_logical_plan(lp, opt_state, custom_optimizations)
     }
 }
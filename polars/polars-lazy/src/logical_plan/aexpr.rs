@@ -1,6 +1,7 @@
 use crate::dsl::function_expr::FunctionExpr;
 use crate::logical_plan::Context;
 use crate::prelude::*;
+use crate::utils::has_aexpr;
 use polars_arrow::prelude::QuantileInterpolOptions;
 use polars_core::prelude::*;
 use polars_core::utils::{get_supertype, get_time_units};
@@ -164,7 +165,7 @@ impl AExpr {
     ) -> Result<Field> {
         use AExpr::*;
         match self {
-            Count => Ok(Field::new("count", DataType::UInt32)),
+            Count => Ok(Field::new("count", IDX_DTYPE)),
             Window { function, .. } => {
                 let e = arena.get(*function);
                 e.to_field(schema, ctxt, arena)
@@ -277,17 +278,17 @@ impl AExpr {
                     }
                     NUnique(expr) => {
                         let mut field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        field.coerce(DataType::UInt32);
+                        field.coerce(IDX_DTYPE);
                         Ok(field)
                     }
                     Count(expr) => {
                         let mut field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        field.coerce(DataType::UInt32);
+                        field.coerce(IDX_DTYPE);
                         Ok(field)
                     }
                     AggGroups(expr) => {
                         let mut field = arena.get(*expr).to_field(schema, ctxt, arena)?;
-                        field.coerce(DataType::List(DataType::UInt32.into()));
+                        field.coerce(DataType::List(IDX_DTYPE.into()));
                         Ok(field)
                     }
                     Quantile { expr, .. } => {
@@ -303,7 +304,28 @@ impl AExpr {
                 let field = arena.get(*expr).to_field(schema, ctxt, arena)?;
                 Ok(Field::new(field.name(), data_type.clone()))
             }
-            Ternary { truthy, falsy, .. } => {
+            Ternary {
+                predicate,
+                truthy,
+                falsy,
+            } => {
+                // the output type of an anonymous function is only a guess
+                let known_dtype = !has_aexpr(*predicate, arena, |e| {
+                    matches!(e, AExpr::AnonymousFunction { .. })
+                });
+                let predicate = arena.get(*predicate).to_field(schema, ctxt, arena)?;
+                if known_dtype
+                    && !matches!(predicate.data_type(), DataType::Boolean | DataType::Null)
+                {
+                    return Err(PolarsError::SchemaMisMatch(
+                        format!(
+                            "a when predicate must be boolean, got {:?} of type {:?}",
+                            predicate.name(),
+                            predicate.data_type()
+                        )
+                        .into(),
+                    ));
+                }
                 let mut truthy = arena.get(*truthy).to_field(schema, ctxt, arena)?;
                 let falsy = arena.get(*falsy).to_field(schema, ctxt, arena)?;
                 if let DataType::Null = *truthy.data_type() {
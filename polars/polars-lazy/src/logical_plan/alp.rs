@@ -2,7 +2,7 @@
 use crate::logical_plan::IpcScanOptions;
 #[cfg(feature = "parquet")]
 use crate::logical_plan::ParquetOptions;
-use crate::logical_plan::{det_melt_schema, Context, CsvParserOptions};
+use crate::logical_plan::{det_join_schema, det_melt_schema, Context, CsvParserOptions};
 use crate::prelude::*;
 use crate::utils::{aexprs_to_schema, PushNode};
 use polars_core::frame::explode::MeltArgs;
@@ -89,6 +89,7 @@ pub enum ALogicalPlan {
     },
     Cache {
         input: Node,
+        id: usize,
     },
     Aggregate {
         input: Node,
@@ -144,7 +145,7 @@ impl ALogicalPlan {
         use ALogicalPlan::*;
         match self {
             Union { inputs, .. } => arena.get(inputs[0]).schema(arena),
-            Cache { input } => arena.get(*input).schema(arena),
+            Cache { input, .. } => arena.get(*input).schema(arena),
             Sort { input, .. } => arena.get(*input).schema(arena),
             Explode { schema, .. } => schema,
             #[cfg(feature = "parquet")]
@@ -260,7 +261,10 @@ impl ALogicalPlan {
                 columns: columns.clone(),
                 schema: schema.clone(),
             },
-            Cache { .. } => Cache { input: inputs[0] },
+            Cache { id, .. } => Cache {
+                input: inputs[0],
+                id: *id,
+            },
             Distinct { options, .. } => Distinct {
                 input: inputs[0],
                 options: options.clone(),
@@ -673,37 +677,13 @@ impl<'a> ALogicalPlanBuilder<'a> {
         let schema_left = self.schema();
         let schema_right = self.lp_arena.get(other).schema(self.lp_arena);
 
-        // column names of left table
-        let mut names: PlHashSet<&str> =
-            PlHashSet::with_capacity(schema_left.len() + schema_right.len());
-        let mut new_schema = Schema::with_capacity(schema_left.len() + schema_right.len());
-
-        for (name, dtype) in schema_left.iter() {
-            names.insert(name.as_str());
-            new_schema.with_column(name.to_string(), dtype.clone())
-        }
-
-        let right_names: PlHashSet<_> = right_on
-            .iter()
-            .map(|e| match self.expr_arena.get(*e) {
-                AExpr::Alias(_, name) => name.clone(),
-                AExpr::Column(name) => name.clone(),
-                _ => panic!("could not determine join column names"),
-            })
-            .collect();
-
-        for (name, dtype) in schema_right.iter() {
-            if !right_names.contains(name.as_str()) {
-                if names.contains(name.as_str()) {
-                    let new_name = format!("{}{}", name, options.suffix.as_ref());
-                    new_schema.with_column(new_name, dtype.clone());
-                } else {
-                    new_schema.with_column(name.to_string(), dtype.clone());
-                }
-            }
-        }
-
-        let schema = Arc::new(new_schema);
+        let right_names = join_key_names(&right_on, self.expr_arena);
+        let schema = Arc::new(det_join_schema(
+            schema_left,
+            schema_right,
+            &right_names,
+            &options,
+        ));
 
         let lp = ALogicalPlan::Join {
             input_left: self.root,
@@ -713,8 +693,18 @@ impl<'a> ALogicalPlanBuilder<'a> {
             right_on,
             options,
         };
-        drop(names);
         let root = self.lp_arena.add(lp);
         Self::new(root, self.expr_arena, self.lp_arena)
     }
 }
+
+/// Get the output names of the join keys `on`.
+pub(crate) fn join_key_names(on: &[Node], expr_arena: &Arena<AExpr>) -> PlHashSet<Arc<str>> {
+    on.iter()
+        .map(|e| match expr_arena.get(*e) {
+            AExpr::Alias(_, name) => name.clone(),
+            AExpr::Column(name) => name.clone(),
+            _ => panic!("could not determine join column names"),
+        })
+        .collect()
+}
@@ -1,4 +1,5 @@
-use crate::logical_plan::projection::rewrite_projections;
+use crate::logical_plan::next_cache_id;
+use crate::logical_plan::projection::{check_excluded, rewrite_projections};
 use crate::prelude::*;
 use crate::utils;
 use crate::utils::{combine_predicates_expr, has_expr};
@@ -23,11 +24,29 @@ use std::io::{Read, Seek, SeekFrom};
 use std::path::PathBuf;
 
 pub(crate) fn prepare_projection(exprs: Vec<Expr>, schema: &Schema) -> Result<(Vec<Expr>, Schema)> {
+    check_excluded(&exprs, schema)?;
     let exprs = rewrite_projections(exprs, schema, &[]);
     let schema = utils::expressions_to_schema(&exprs, schema, Context::Default)?;
     Ok((exprs, schema))
 }
 
+/// Check that the columns used by the join keys exist in the schema of that side of the join.
+fn check_join_keys(keys: &[Expr], schema: &Schema, side: &str) -> Result<()> {
+    for key in keys {
+        for name in utils::expr_to_root_column_names(key) {
+            if schema.get(&name).is_none() {
+                return Err(PolarsError::NotFound(format!(
+                    "join key {} not found in the {} table; available columns: {:?}",
+                    name,
+                    side,
+                    schema.iter_names().collect::<Vec<_>>()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub struct LogicalPlanBuilder(LogicalPlan);
 
 impl From<LogicalPlan> for LogicalPlanBuilder {
@@ -51,6 +70,22 @@ macro_rules! try_delayed {
     };
 }
 
+/// Check that a sort has a flag per key. A single flag is used for all keys.
+fn sort_flags(flags: Vec<bool>, n_keys: usize, name: &str) -> Result<Vec<bool>> {
+    if n_keys == 0 {
+        return Err(PolarsError::ComputeError(
+            "a sort needs at least one key".into(),
+        ));
+    }
+    match flags.len() {
+        n if n == n_keys => Ok(flags),
+        1 => Ok(vec![flags[0]; n_keys]),
+        n => Err(PolarsError::ComputeError(
+            format!("a sort by {} keys got {} values for {}", n_keys, n, name).into(),
+        )),
+    }
+}
+
 impl LogicalPlanBuilder {
     #[cfg(feature = "parquet")]
     #[cfg_attr(docsrs, doc(cfg(feature = "parquet")))]
@@ -185,6 +220,7 @@ impl LogicalPlanBuilder {
     pub fn cache(self) -> Self {
         LogicalPlan::Cache {
             input: Box::new(self.0),
+            id: next_cache_id(),
         }
         .into()
     }
@@ -221,6 +257,27 @@ impl LogicalPlanBuilder {
         .into()
     }
 
+    /// Project all columns except the dropped ones.
+    /// Dropping a column that does not exist is an error.
+    pub fn drop_columns(self, names: &[String]) -> Self {
+        let schema = self.0.schema();
+        for name in names {
+            try_delayed!(
+                schema
+                    .get(name)
+                    .ok_or_else(|| PolarsError::NotFound(name.clone())),
+                &self.0,
+                into
+            );
+        }
+        let exprs = schema
+            .iter_names()
+            .filter(|name| !names.contains(name))
+            .map(|name| col(name))
+            .collect();
+        self.project(exprs)
+    }
+
     pub fn fill_null(self, fill_value: Expr) -> Self {
         let schema = self.0.schema();
         let exprs = schema
@@ -299,6 +356,7 @@ impl LogicalPlanBuilder {
         rolling_options: Option<RollingGroupOptions>,
     ) -> Self {
         let current_schema = self.0.schema();
+        try_delayed!(check_excluded(aggs.as_ref(), current_schema), &self.0, into);
         let aggs = rewrite_projections(aggs.as_ref().to_vec(), current_schema, keys.as_ref());
 
         let mut schema = try_delayed!(
@@ -363,13 +421,16 @@ impl LogicalPlanBuilder {
         .into()
     }
 
-    pub fn sort(self, by_column: Vec<Expr>, reverse: Vec<bool>, null_last: bool) -> Self {
+    pub fn sort(self, by_column: Vec<Expr>, reverse: Vec<bool>, nulls_last: Vec<bool>) -> Self {
+        let n_keys = by_column.len();
+        let reverse = try_delayed!(sort_flags(reverse, n_keys, "reverse"), &self.0, into);
+        let nulls_last = try_delayed!(sort_flags(nulls_last, n_keys, "nulls_last"), &self.0, into);
         LogicalPlan::Sort {
             input: Box::new(self.0),
             by_column,
             args: SortArguments {
                 reverse,
-                nulls_last: null_last,
+                nulls_last,
                 slice: None,
             },
         }
@@ -442,34 +503,28 @@ impl LogicalPlanBuilder {
         let schema_left = self.0.schema();
         let schema_right = other.schema();
 
-        // column names of left table
-        let mut names: PlHashSet<&str> = PlHashSet::default();
-        let mut new_schema = Schema::with_capacity(schema_left.len() + schema_right.len());
-
-        for (name, dtype) in schema_left.iter() {
-            names.insert(name);
-            new_schema.with_column(name.to_string(), dtype.clone())
-        }
+        try_delayed!(
+            check_join_keys(&left_on, schema_left, "left"),
+            &self.0,
+            into
+        );
+        try_delayed!(
+            check_join_keys(&right_on, schema_right, "right"),
+            &self.0,
+            into
+        );
 
         let right_names: PlHashSet<_> = right_on
             .iter()
             .map(|e| utils::expr_output_name(e).expect("could not find name"))
             .collect();
+        let schema = Arc::new(det_join_schema(
+            schema_left,
+            schema_right,
+            &right_names,
+            &options,
+        ));
 
-        for (name, dtype) in schema_right.iter() {
-            if !right_names.iter().any(|s| s.as_ref() == name) {
-                if names.contains(&**name) {
-                    let new_name = format!("{}{}", name, options.suffix.as_ref());
-                    new_schema.with_column(new_name, dtype.clone())
-                } else {
-                    new_schema.with_column(name.to_string(), dtype.clone())
-                }
-            }
-        }
-
-        let schema = Arc::new(new_schema);
-
-        drop(names);
         LogicalPlan::Join {
             input_left: Box::new(self.0),
             input_right: Box::new(other),
@@ -506,6 +561,76 @@ impl LogicalPlanBuilder {
     }
 }
 
+/// The input of a join that a column of its output comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JoinSide {
+    Left,
+    Right,
+}
+
+/// A column of the output of a join.
+pub(crate) struct JoinColumn<'a> {
+    pub(crate) name: String,
+    pub(crate) side: JoinSide,
+    /// The name of the column in the input it comes from.
+    pub(crate) input_name: &'a str,
+    pub(crate) dtype: &'a DataType,
+}
+
+/// Determine the columns of the output of a join, in order: the columns of the left input,
+/// followed by the columns of the right input that are not a join key. A column of the right
+/// input that has the name of a column of the left input gets the suffix of the join.
+pub(crate) fn det_join_columns<'a>(
+    schema_left: &'a Schema,
+    schema_right: &'a Schema,
+    right_names: &PlHashSet<Arc<str>>,
+    options: &JoinOptions,
+) -> Vec<JoinColumn<'a>> {
+    let mut columns = Vec::with_capacity(schema_left.len() + schema_right.len());
+    for (name, dtype) in schema_left.iter() {
+        columns.push(JoinColumn {
+            name: name.clone(),
+            side: JoinSide::Left,
+            input_name: name,
+            dtype,
+        })
+    }
+
+    // a semi or anti join only has the columns of the left table
+    if !options.is_semi_anti() {
+        for (name, dtype) in schema_right.iter() {
+            if !right_names.contains(name.as_str()) {
+                let output_name = if schema_left.get(name).is_some() {
+                    format!("{}{}", name, options.suffix.as_ref())
+                } else {
+                    name.clone()
+                };
+                columns.push(JoinColumn {
+                    name: output_name,
+                    side: JoinSide::Right,
+                    input_name: name,
+                    dtype,
+                })
+            }
+        }
+    }
+    columns
+}
+
+pub(crate) fn det_join_schema(
+    schema_left: &Schema,
+    schema_right: &Schema,
+    right_names: &PlHashSet<Arc<str>>,
+    options: &JoinOptions,
+) -> Schema {
+    let columns = det_join_columns(schema_left, schema_right, right_names, options);
+    let mut schema = Schema::with_capacity(columns.len());
+    for column in columns {
+        schema.with_column(column.name, column.dtype.clone())
+    }
+    schema
+}
+
 pub(crate) fn det_melt_schema(args: &MeltArgs, input_schema: &Schema) -> SchemaRef {
     let mut new_schema = Schema::from(
         args.id_vars
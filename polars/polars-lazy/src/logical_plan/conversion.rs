@@ -165,7 +165,26 @@ pub(crate) fn to_alp(
             ALogicalPlan::Union { inputs, options }
         }
         LogicalPlan::Selection { input, predicate } => {
-            let i = to_alp(*input, expr_arena, lp_arena)?;
+            // stacked selections are converted in a loop, so that long chains don't overflow
+            // the stack
+            let mut predicates = vec![];
+            let mut input = *input;
+            while let LogicalPlan::Selection {
+                input: inner,
+                predicate,
+            } = input
+            {
+                predicates.push(predicate);
+                input = *inner;
+            }
+            let mut i = to_alp(input, expr_arena, lp_arena)?;
+            for predicate in predicates.into_iter().rev() {
+                let p = to_aexpr(predicate, expr_arena);
+                i = lp_arena.add(ALogicalPlan::Selection {
+                    input: i,
+                    predicate: p,
+                });
+            }
             let p = to_aexpr(predicate, expr_arena);
             ALogicalPlan::Selection {
                 input: i,
@@ -308,9 +327,9 @@ pub(crate) fn to_alp(
                 schema,
             }
         }
-        LogicalPlan::Cache { input } => {
+        LogicalPlan::Cache { input, id } => {
             let input = to_alp(*input, expr_arena, lp_arena)?;
-            ALogicalPlan::Cache { input }
+            ALogicalPlan::Cache { input, id }
         }
         LogicalPlan::Aggregate {
             input,
@@ -660,12 +679,26 @@ pub(crate) fn node_to_lp(
             }
         }
         ALogicalPlan::Selection { input, predicate } => {
-            let lp = node_to_lp(input, expr_arena, lp_arena);
-            let p = node_to_expr(predicate, expr_arena);
-            LogicalPlan::Selection {
-                input: Box::new(lp),
-                predicate: p,
-            }
+            // stacked selections are converted in a loop, so that long chains don't overflow
+            // the stack
+            let mut predicates = vec![node_to_expr(predicate, expr_arena)];
+            let mut input = input;
+            while let ALogicalPlan::Selection {
+                input: inner,
+                predicate,
+            } = lp_arena.get(input)
+            {
+                predicates.push(node_to_expr(*predicate, expr_arena));
+                input = *inner;
+            }
+            let mut lp = node_to_lp(input, expr_arena, lp_arena);
+            for predicate in predicates.into_iter().rev() {
+                lp = LogicalPlan::Selection {
+                    input: Box::new(lp),
+                    predicate,
+                };
+            }
+            lp
         }
         #[cfg(feature = "csv-file")]
         ALogicalPlan::CsvScan {
@@ -775,9 +808,9 @@ pub(crate) fn node_to_lp(
                 schema,
             }
         }
-        ALogicalPlan::Cache { input } => {
+        ALogicalPlan::Cache { input, id } => {
             let input = Box::new(node_to_lp(input, expr_arena, lp_arena));
-            LogicalPlan::Cache { input }
+            LogicalPlan::Cache { input, id }
         }
         ALogicalPlan::Aggregate {
             input,
@@ -3,12 +3,24 @@ use std::borrow::Cow;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 
-impl fmt::Debug for LogicalPlan {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl LogicalPlan {
+    /// Format the plan with the inputs of every node indented below that node.
+    fn _format(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let sub_indent = indent + 2;
         use LogicalPlan::*;
         match self {
-            Union { inputs, .. } => write!(f, "UNION {:?}", inputs),
-            Cache { input } => write!(f, "CACHE {:?}", input),
+            Union { inputs, .. } => {
+                write!(f, "{:indent$}UNION:", "", indent = indent)?;
+                for (i, plan) in inputs.iter().enumerate() {
+                    writeln!(f, "\n{:indent$}PLAN {}:", "", i, indent = sub_indent)?;
+                    plan._format(f, sub_indent + 2)?;
+                }
+                write!(f, "\n{:indent$}END UNION", "", indent = indent)
+            }
+            Cache { input, id } => {
+                writeln!(f, "{:indent$}CACHE {}", "", id, indent = indent)?;
+                input._format(f, sub_indent)
+            }
             #[cfg(feature = "parquet")]
             ParquetScan {
                 path,
@@ -24,11 +36,13 @@ impl fmt::Debug for LogicalPlan {
                 }
                 write!(
                     f,
-                    "PARQUET SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "{:indent$}PARQUET SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "",
                     path.to_string_lossy(),
                     n_columns,
                     total_columns,
-                    predicate
+                    predicate,
+                    indent = indent
                 )
             }
             #[cfg(feature = "ipc")]
@@ -46,18 +60,29 @@ impl fmt::Debug for LogicalPlan {
                 }
                 write!(
                     f,
-                    "IPC SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "{:indent$}IPC SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "",
                     path.to_string_lossy(),
                     n_columns,
                     total_columns,
-                    predicate
+                    predicate,
+                    indent = indent
                 )
             }
             Selection { predicate, input } => {
-                write!(f, "FILTER {:?}\nFROM\n{:?}", predicate, input)
+                writeln!(
+                    f,
+                    "{:indent$}FILTER {:?}\n{:indent$}FROM",
+                    "",
+                    predicate,
+                    "",
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
             }
             Melt { input, .. } => {
-                write!(f, "MELT {:?}", input)
+                writeln!(f, "{:indent$}MELT", "", indent = indent)?;
+                input._format(f, sub_indent)
             }
             #[cfg(feature = "csv-file")]
             CsvScan {
@@ -74,11 +99,13 @@ impl fmt::Debug for LogicalPlan {
                 }
                 write!(
                     f,
-                    "CSV SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "{:indent$}CSV SCAN {}; PROJECT {}/{} COLUMNS; SELECTION: {:?}",
+                    "",
                     path.to_string_lossy(),
                     n_columns,
                     total_columns,
-                    predicate
+                    predicate,
+                    indent = indent
                 )
             }
             DataFrameScan {
@@ -99,67 +126,135 @@ impl fmt::Debug for LogicalPlan {
 
                 write!(
                     f,
-                    "DATAFRAME(in-memory): {:?};\n\tproject {}/{} columns\t|\tdetails: {:?};\n\
-                    \tselection: {:?}\n\n",
+                    "{:indent$}DATAFRAME(in-memory): {:?};\n{:indent$}\tproject {}/{} columns\t|\tdetails: {:?};\n\
+                    {:indent$}\tselection: {:?}",
+                    "",
                     schema.iter_names().take(4).collect::<Vec<_>>(),
+                    "",
                     n_columns,
                     total_columns,
                     projection,
+                    "",
                     selection,
+                    indent = indent
                 )
             }
             Projection { expr, input, .. } => {
-                write!(
+                writeln!(
                     f,
-                    "SELECT {:?} COLUMNS: {:?}
-FROM
-{:?}",
+                    "{:indent$}SELECT {:?} COLUMNS: {:?}\n{:indent$}FROM",
+                    "",
                     expr.len(),
                     expr,
-                    input
-                )
+                    "",
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
             }
             LocalProjection { expr, input, .. } => {
-                write!(
+                writeln!(
                     f,
-                    "LOCAL SELECT {:?} COLUMNS \nFROM\n{:?}",
+                    "{:indent$}LOCAL SELECT {:?} COLUMNS\n{:indent$}FROM",
+                    "",
                     expr.len(),
-                    input
-                )
+                    "",
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
             }
             Sort {
                 input, by_column, ..
-            } => write!(f, "SORT {:?} BY {:?}", input, by_column),
+            } => {
+                writeln!(f, "{:indent$}SORT BY {:?}", "", by_column, indent = indent)?;
+                input._format(f, sub_indent)
+            }
             Explode { input, columns, .. } => {
-                write!(f, "EXPLODE COLUMN(S) {:?} OF {:?}", columns, input)
+                writeln!(
+                    f,
+                    "{:indent$}EXPLODE COLUMN(S) {:?}",
+                    "",
+                    columns,
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
             }
             Aggregate {
                 input, keys, aggs, ..
-            } => write!(f, "Aggregate\n\t{:?} BY {:?} FROM {:?}", aggs, keys, input),
+            } => {
+                writeln!(
+                    f,
+                    "{:indent$}AGGREGATE\n{:indent$}\t{:?} BY {:?} FROM",
+                    "",
+                    "",
+                    aggs,
+                    keys,
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
+            }
             Join {
                 input_left,
                 input_right,
                 left_on,
                 right_on,
                 ..
-            } => write!(
-                f,
-                "JOIN\n\t({:?})\nWITH\n\t({:?})\nON (left: {:?} right: {:?})",
-                input_left, input_right, left_on, right_on
-            ),
+            } => {
+                writeln!(f, "{:indent$}JOIN:", "", indent = indent)?;
+                writeln!(
+                    f,
+                    "{:indent$}LEFT PLAN ON: {:?}",
+                    "",
+                    left_on,
+                    indent = sub_indent
+                )?;
+                input_left._format(f, sub_indent + 2)?;
+                writeln!(
+                    f,
+                    "\n{:indent$}RIGHT PLAN ON: {:?}",
+                    "",
+                    right_on,
+                    indent = sub_indent
+                )?;
+                input_right._format(f, sub_indent + 2)?;
+                write!(f, "\n{:indent$}END JOIN", "", indent = indent)
+            }
             HStack { input, exprs, .. } => {
-                write!(f, "{:?}\nWITH COLUMNS {:?}", input, exprs)
+                writeln!(f, "{:indent$}WITH COLUMNS {:?}", "", exprs, indent = indent)?;
+                input._format(f, sub_indent)
+            }
+            Distinct { input, .. } => {
+                writeln!(f, "{:indent$}DISTINCT", "", indent = indent)?;
+                input._format(f, sub_indent)
             }
-            Distinct { input, .. } => write!(f, "DISTINCT {:?}", input),
             Slice { input, offset, len } => {
-                write!(f, "{:?}\nSLICE[offset: {}, len: {}]", input, offset, len)
+                writeln!(
+                    f,
+                    "{:indent$}SLICE[offset: {}, len: {}]",
+                    "",
+                    offset,
+                    len,
+                    indent = indent
+                )?;
+                input._format(f, sub_indent)
+            }
+            Udf { input, options, .. } => {
+                writeln!(f, "{:indent$}{}", "", options.fmt_str, indent = indent)?;
+                input._format(f, sub_indent)
+            }
+            Error { input, err } => {
+                writeln!(f, "{:indent$}{:?}", "", err, indent = indent)?;
+                input._format(f, sub_indent)
             }
-            Udf { input, options, .. } => write!(f, "{} \n{:?}", options.fmt_str, input),
-            Error { input, err } => write!(f, "{:?}\n{:?}", err, input),
         }
     }
 }
 
+impl fmt::Debug for LogicalPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self._format(f, 0)
+    }
+}
+
 impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::*;
@@ -240,6 +335,11 @@ impl fmt::Debug for Expr {
                 "\nWHEN {:?}\n\t{:?}\nOTHERWISE\n\t{:?}",
                 predicate, truthy, falsy
             ),
+            // the function is shown with its arguments, so that functions with other arguments
+            // are formatted differently
+            Function {
+                input, function, ..
+            } if input.len() == 1 => write!(f, "{:?}.{}", input[0], function),
             AnonymousFunction { input, options, .. } | Function { input, options, .. } => {
                 if input.len() >= 2 {
                     write!(f, "{:?}.{}({:?})", input[0], options.fmt_str, &input[1..])
@@ -32,6 +32,13 @@ use serde::{Deserialize, Serialize};
 // Will be set/ unset in the fetch operation to communicate overwriting the number of rows to scan.
 thread_local! {pub(crate) static FETCH_ROWS: Cell<Option<usize>> = Cell::new(None)}
 
+/// Get a new id for a `Cache` node.
+pub(crate) fn next_cache_id() -> usize {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static CACHE_ID: AtomicUsize = AtomicUsize::new(0);
+    CACHE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Context {
     /// Any operation that is done on groups
@@ -53,8 +60,8 @@ pub enum LogicalPlan {
         input: Box<LogicalPlan>,
         predicate: Expr,
     },
-    /// Cache the input at this point in the LP
-    Cache { input: Box<LogicalPlan> },
+    /// Cache the input at this point in the LP. Caches with the same `id` share their result.
+    Cache { input: Box<LogicalPlan>, id: usize },
     /// Scan a CSV file
     #[cfg(feature = "csv-file")]
     CsvScan {
@@ -209,7 +216,7 @@ impl LogicalPlan {
         use LogicalPlan::*;
         match self {
             Union { inputs, .. } => inputs[0].schema(),
-            Cache { input } => input.schema(),
+            Cache { input, .. } => input.schema(),
             Sort { input, .. } => input.schema(),
             Explode { schema, .. } => schema,
             #[cfg(feature = "parquet")]
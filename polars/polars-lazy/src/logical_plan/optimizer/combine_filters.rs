@@ -0,0 +1,51 @@
+use crate::logical_plan::optimizer::predicate_pushdown::is_expensive_predicate;
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+use crate::utils::aexpr_is_elementwise;
+
+/// Combine a selection directly on top of another selection into a single selection by
+/// combining the predicates with `AND`. This saves filtering all the columns twice.
+///
+/// The upper predicate is evaluated on the rows that remain after the lower predicate, so this is
+/// only done if the upper predicate is element wise. `A AND null` is `null` and `null` rows are
+/// filtered out, so the combined filter keeps exactly the rows both filters keep.
+///
+/// An expensive upper predicate on top of a cheap lower predicate is not combined, so that it is
+/// only evaluated on the rows that pass the lower predicate. Predicate pushdown splits filters in
+/// this way.
+pub(crate) struct CombineFilters {}
+
+impl OptimizationRule for CombineFilters {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        use ALogicalPlan::*;
+        let (input, predicate) = match lp_arena.get(node) {
+            Selection { input, predicate } => (*input, *predicate),
+            _ => return None,
+        };
+        match lp_arena.get(input) {
+            Selection {
+                input,
+                predicate: lower_predicate,
+            } if aexpr_is_elementwise(predicate, expr_arena)
+                && (!is_expensive_predicate(predicate, expr_arena)
+                    || is_expensive_predicate(*lower_predicate, expr_arena)) =>
+            {
+                let predicate = expr_arena.add(AExpr::BinaryExpr {
+                    left: *lower_predicate,
+                    op: Operator::And,
+                    right: predicate,
+                });
+                Some(Selection {
+                    input: *input,
+                    predicate,
+                })
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::logical_plan::conversion::node_to_expr;
+use crate::logical_plan::next_cache_id;
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+use polars_core::prelude::*;
+
+/// Execute subplans that occur in both inputs of a join, or in multiple inputs of a union, only
+/// once. Both occurrences are wrapped in a `Cache` with the same id, so that the second one reuses
+/// the result of the first one.
+///
+/// This should run after the pushdown optimizations, as these can make the occurrences differ.
+pub(crate) struct CommonSubplanElim {}
+
+fn exprs_equal(a: &[Node], b: &[Node], expr_arena: &Arena<AExpr>) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| node_to_expr(*a, expr_arena) == node_to_expr(*b, expr_arena))
+}
+
+/// Check if two subplans are structurally equal. Plans of which the equality is not known, such
+/// as scans of files, are considered different.
+fn plans_equal(
+    a: Node,
+    b: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    use ALogicalPlan::*;
+    let lp_a = lp_arena.get(a);
+    let lp_b = lp_arena.get(b);
+    let same_node = match (lp_a, lp_b) {
+        (DataFrameScan { df: df_a, .. }, DataFrameScan { df: df_b, .. }) => Arc::ptr_eq(df_a, df_b),
+        (Selection { .. }, Selection { .. })
+        | (Projection { .. }, Projection { .. })
+        | (LocalProjection { .. }, LocalProjection { .. })
+        | (HStack { .. }, HStack { .. }) => true,
+        // the sort keys are not part of `get_exprs`
+        (
+            Sort {
+                by_column: by_a,
+                args: args_a,
+                ..
+            },
+            Sort {
+                by_column: by_b,
+                args: args_b,
+                ..
+            },
+        ) => args_a == args_b && exprs_equal(by_a, by_b, expr_arena),
+        (
+            Slice {
+                offset: offset_a,
+                len: len_a,
+                ..
+            },
+            Slice {
+                offset: offset_b,
+                len: len_b,
+                ..
+            },
+        ) => offset_a == offset_b && len_a == len_b,
+        (Explode { columns: a, .. }, Explode { columns: b, .. }) => a == b,
+        (
+            Udf {
+                function: function_a,
+                options: options_a,
+                ..
+            },
+            Udf {
+                function: function_b,
+                options: options_b,
+                ..
+            },
+        ) => {
+            // compare the data pointers, the vtables may differ
+            Arc::as_ptr(function_a) as *const u8 == Arc::as_ptr(function_b) as *const u8
+                && options_a == options_b
+        }
+        (Cache { id: id_a, .. }, Cache { id: id_b, .. }) => id_a == id_b,
+        _ => false,
+    };
+    if !same_node || !exprs_equal(&lp_a.get_exprs(), &lp_b.get_exprs(), expr_arena) {
+        return false;
+    }
+    let inputs_a = lp_a.get_inputs();
+    let inputs_b = lp_b.get_inputs();
+    inputs_a.len() == inputs_b.len()
+        && inputs_a
+            .iter()
+            .zip(&inputs_b)
+            .all(|(a, b)| plans_equal(*a, *b, lp_arena, expr_arena))
+}
+
+/// Check if a subplan is worth caching. Subplans that are already cached are not searched.
+fn is_candidate(node: Node, lp_arena: &Arena<ALogicalPlan>) -> bool {
+    !matches!(
+        lp_arena.get(node),
+        ALogicalPlan::Cache { .. }
+            | ALogicalPlan::DataFrameScan {
+                selection: None,
+                ..
+            }
+    )
+}
+
+/// Push the nodes of a subplan in pre-order, without the inputs of caches.
+fn collect_subplans(root: Node, lp_arena: &Arena<ALogicalPlan>, nodes: &mut Vec<Node>) {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let lp = lp_arena.get(node);
+        nodes.push(node);
+        if !matches!(lp, ALogicalPlan::Cache { .. }) {
+            // reversed so that the first input is popped first
+            let mut inputs = lp.get_inputs();
+            inputs.reverse();
+            stack.extend(inputs);
+        }
+    }
+}
+
+/// Find the largest subplan of `b` that is equal to a subplan of `a`.
+fn find_common_subplan(
+    a: Node,
+    b: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<(Node, Node)> {
+    let mut nodes_a = vec![];
+    collect_subplans(a, lp_arena, &mut nodes_a);
+    nodes_a.retain(|node| is_candidate(*node, lp_arena));
+    let mut nodes_b = vec![];
+    collect_subplans(b, lp_arena, &mut nodes_b);
+
+    nodes_b
+        .into_iter()
+        .filter(|node| is_candidate(*node, lp_arena))
+        .find_map(|node_b| {
+            nodes_a
+                .iter()
+                .find(|node_a| {
+                    **node_a != node_b && plans_equal(**node_a, node_b, lp_arena, expr_arena)
+                })
+                .map(|node_a| (*node_a, node_b))
+        })
+}
+
+/// Wrap the subplan at `node` in a cache. The node stays at the same position in the arena.
+fn insert_cache(node: Node, id: usize, lp_arena: &mut Arena<ALogicalPlan>) {
+    let lp = lp_arena.take(node);
+    let input = lp_arena.add(lp);
+    lp_arena.replace(node, ALogicalPlan::Cache { input, id });
+}
+
+impl OptimizationRule for CommonSubplanElim {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        let inputs = match lp_arena.get(node) {
+            ALogicalPlan::Join {
+                input_left,
+                input_right,
+                ..
+            } => vec![*input_left, *input_right],
+            ALogicalPlan::Union { inputs, .. } => inputs.clone(),
+            _ => return None,
+        };
+
+        for (i, a) in inputs.iter().enumerate() {
+            for b in &inputs[i + 1..] {
+                if let Some((a, b)) = find_common_subplan(*a, *b, lp_arena, expr_arena) {
+                    let id = next_cache_id();
+                    insert_cache(a, id, lp_arena);
+                    insert_cache(b, id, lp_arena);
+                    // the inputs of this node are changed in place
+                    return Some(lp_arena.get(node).clone());
+                }
+            }
+        }
+        None
+    }
+}
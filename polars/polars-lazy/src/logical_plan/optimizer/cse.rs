@@ -0,0 +1,212 @@
+use crate::logical_plan::conversion::{node_to_expr, to_aexpr};
+use crate::logical_plan::Context;
+use crate::prelude::iterator::ArenaExprIter;
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+use crate::utils::{aexpr_is_elementwise, has_aexpr};
+use polars_core::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Compute the subexpressions that occur more than once in a projection, or in the aggregations
+/// of a groupby, only once.
+///
+/// ```text
+/// SELECT [(col("a") * col("b")).alias("x"), (col("a") * col("b") + lit(1)).alias("y")]
+///
+/// becomes
+///
+/// SELECT [col("__POLARS_CSE_0").alias("x"), (col("__POLARS_CSE_0") + lit(1)).alias("y")]
+///     WITH COLUMNS [(col("a") * col("b")).alias("__POLARS_CSE_0")]
+/// ```
+///
+/// Only element wise subexpressions are computed on the input, as these give the same result
+/// before a projection or aggregation.
+pub(crate) struct CommonSubExprElim {}
+
+const CSE_PREFIX: &str = "__POLARS_CSE_";
+
+/// Check if a subexpression can be computed on the input and is worth computing only once.
+fn is_candidate(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    !matches!(
+        expr_arena.get(node),
+        AExpr::Column(_) | AExpr::Literal(_) | AExpr::Alias(_, _)
+    ) && aexpr_is_elementwise(node, expr_arena)
+        && has_aexpr(node, expr_arena, |e| matches!(e, AExpr::Column(_)))
+}
+
+/// Get the subexpressions that occur more than once in `exprs`.
+fn common_subexprs(exprs: &[Node], expr_arena: &Arena<AExpr>) -> Vec<Expr> {
+    // `Expr` is not `Eq` (it holds floats and functions), so it cannot be a key of a hashmap.
+    // The number of subexpressions in a projection is small, so they are compared one by one, and
+    // only if their hashes are equal.
+    let mut counts: Vec<(u64, Expr, usize)> = vec![];
+    for node in exprs {
+        for (node, _) in expr_arena.iter(*node) {
+            if is_candidate(node, expr_arena) {
+                let expr = node_to_expr(node, expr_arena);
+                let mut hasher = DefaultHasher::new();
+                expr.hash(&mut hasher);
+                let hash = hasher.finish();
+                match counts.iter_mut().find(|(h, e, _)| *h == hash && e == &expr) {
+                    Some((_, _, count)) => *count += 1,
+                    None => counts.push((hash, expr, 1)),
+                }
+            }
+        }
+    }
+
+    let repeated = counts
+        .iter()
+        .filter(|(_, _, count)| *count > 1)
+        .map(|(_, expr, count)| (expr, *count))
+        .collect::<Vec<_>>();
+    // a subexpression that only occurs in a larger common subexpression is computed with it
+    repeated
+        .iter()
+        .filter(|&&(expr, count)| {
+            !repeated.iter().any(|&(other, other_count)| {
+                other_count == count && other != expr && other.into_iter().any(|e| e == expr)
+            })
+        })
+        .map(|(expr, _)| (*expr).clone())
+        .collect()
+}
+
+/// Replace the common subexpressions with the columns they are computed in and keep the output
+/// name of the expression.
+fn replace_common_subexprs(
+    node: Node,
+    common: &[(Expr, Arc<str>)],
+    input_schema: &Schema,
+    ctxt: Context,
+    expr_arena: &mut Arena<AExpr>,
+) -> Option<Node> {
+    let original = node_to_expr(node, expr_arena);
+    let mut expr = original.clone();
+    expr.mutate().apply(|e| {
+        if let Some((_, name)) = common.iter().find(|(common, _)| common == e) {
+            *e = Expr::Column(name.clone());
+        }
+        // always keep iterating all inputs
+        true
+    });
+    if expr == original {
+        return Some(node);
+    }
+    if !matches!(expr, Expr::Alias(_, _)) {
+        let field = expr_arena
+            .get(node)
+            .to_field(input_schema, ctxt, expr_arena)
+            .ok()?;
+        expr = expr.alias(field.name());
+    }
+    Some(to_aexpr(expr, expr_arena))
+}
+
+/// Compute the common subexpressions of `exprs` in a `WITH COLUMNS` on top of `input` and rewrite
+/// `exprs` to use those columns.
+fn eliminate(
+    input: Node,
+    exprs: &[Node],
+    ctxt: Context,
+    lp_arena: &mut Arena<ALogicalPlan>,
+    expr_arena: &mut Arena<AExpr>,
+) -> Option<(Node, Vec<Node>)> {
+    let common = common_subexprs(exprs, expr_arena);
+    if common.is_empty() {
+        return None;
+    }
+    let input_schema = lp_arena.get(input).schema(lp_arena).clone();
+
+    let mut idx = 0;
+    let common = common
+        .into_iter()
+        .map(|expr| loop {
+            let name = format!("{}{}", CSE_PREFIX, idx);
+            idx += 1;
+            if input_schema.get(&name).is_none() {
+                break (expr, Arc::from(name));
+            }
+        })
+        .collect::<Vec<(Expr, Arc<str>)>>();
+
+    let mut schema = input_schema.clone();
+    let mut hstack_exprs = Vec::with_capacity(common.len());
+    for (expr, name) in &common {
+        let node = to_aexpr(expr.clone().alias(name), expr_arena);
+        let field = expr_arena
+            .get(node)
+            .to_field(&input_schema, Context::Default, expr_arena)
+            .ok()?;
+        schema.with_column(field.name().clone(), field.data_type().clone());
+        hstack_exprs.push(node);
+    }
+
+    let exprs = exprs
+        .iter()
+        .map(|node| replace_common_subexprs(*node, &common, &input_schema, ctxt, expr_arena))
+        .collect::<Option<Vec<_>>>()?;
+    let input = lp_arena.add(ALogicalPlan::HStack {
+        input,
+        exprs: hstack_exprs,
+        schema: Arc::new(schema),
+    });
+    Some((input, exprs))
+}
+
+impl OptimizationRule for CommonSubExprElim {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        use ALogicalPlan::*;
+        match lp_arena.get(node) {
+            Projection {
+                input,
+                expr,
+                schema,
+            } => {
+                let (input, expr, schema) = (*input, expr.clone(), schema.clone());
+                let (input, expr) =
+                    eliminate(input, &expr, Context::Default, lp_arena, expr_arena)?;
+                Some(Projection {
+                    input,
+                    expr,
+                    schema,
+                })
+            }
+            // the output of a custom apply function cannot be rewritten
+            Aggregate {
+                input,
+                keys,
+                aggs,
+                schema,
+                apply: None,
+                maintain_order,
+                options,
+            } => {
+                let input = *input;
+                let keys = keys.clone();
+                let aggs = aggs.clone();
+                let schema = schema.clone();
+                let maintain_order = *maintain_order;
+                let options = options.clone();
+                let (input, aggs) =
+                    eliminate(input, &aggs, Context::Aggregation, lp_arena, expr_arena)?;
+                Some(Aggregate {
+                    input,
+                    keys,
+                    aggs,
+                    schema,
+                    apply: None,
+                    maintain_order,
+                    options,
+                })
+            }
+            _ => None,
+        }
+    }
+}
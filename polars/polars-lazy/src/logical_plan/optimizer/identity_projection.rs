@@ -0,0 +1,54 @@
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+
+/// Remove projections that select all the columns of their input, unchanged and in the same
+/// order. Such a projection only clones all the columns in the physical plan.
+///
+/// Projections that reorder or rename (alias) columns are kept.
+pub(crate) struct RemoveIdentityProjection {}
+
+/// Check if `expr` selects all the columns of `schema`, unchanged and in the same order.
+pub(crate) fn projects_all_columns(
+    expr: &[Node],
+    schema: &Schema,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    expr.len() == schema.len()
+        && expr
+            .iter()
+            .zip(schema.iter_names())
+            .all(|(node, input_name)| match expr_arena.get(*node) {
+                AExpr::Column(name) => &**name == input_name.as_str(),
+                _ => false,
+            })
+}
+
+fn is_identity_projection(
+    expr: &[Node],
+    input: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    let input_schema = lp_arena.get(input).schema(lp_arena);
+    projects_all_columns(expr, input_schema, expr_arena)
+}
+
+impl OptimizationRule for RemoveIdentityProjection {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        use ALogicalPlan::*;
+        let input = match lp_arena.get(node) {
+            Projection { input, expr, .. } | LocalProjection { input, expr, .. }
+                if is_identity_projection(expr, *input, lp_arena, expr_arena) =>
+            {
+                *input
+            }
+            _ => return None,
+        };
+        Some(lp_arena.take(input))
+    }
+}
@@ -0,0 +1,209 @@
+use crate::logical_plan::conversion::{node_to_expr, to_aexpr};
+use crate::logical_plan::Context;
+use crate::prelude::iterator::ArenaExprIter;
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+use crate::utils::{aexpr_is_elementwise, has_aexpr};
+use polars_core::prelude::*;
+
+/// Merge a projection into the projection below it by substituting the expressions of the lower
+/// projection for the columns the upper projection refers to. This saves the materialization of
+/// an intermediate `DataFrame`.
+///
+/// ```text
+/// SELECT [col("b"), col("foo").alias("bar")]
+///     SELECT [(col("a") * lit(2)).alias("foo"), col("b")]
+///
+/// becomes
+///
+/// SELECT [col("b"), (col("a") * lit(2)).alias("bar")]
+/// ```
+///
+/// Columns of the lower projection may only be dropped if all its expressions are column
+/// selections. Other expressions may broadcast or aggregate, which depends on the expressions
+/// that are projected alongside them.
+///
+/// If the upper projection computes new values, the lower expressions are only substituted if
+/// they are all element wise and refer to a column, so that they have the length of the input.
+/// A computed lower expression that is used more than once is not substituted, as it would be
+/// computed twice.
+pub(crate) struct MergeProjections {}
+
+/// Get the expression of the lower projection that produces `name` and (re)alias it if needed.
+fn lower_expr(
+    name: &Arc<str>,
+    output_name: &Arc<str>,
+    lower_expr: &[Node],
+    lower_input_schema: &Schema,
+    expr_arena: &mut Arena<AExpr>,
+) -> Option<(usize, Node)> {
+    let idx = lower_expr.iter().position(|node| {
+        expr_arena
+            .get(*node)
+            .to_field(lower_input_schema, Context::Default, expr_arena)
+            .map(|field| field.name().as_str() == &**name)
+            .unwrap_or(false)
+    })?;
+    let node = lower_expr[idx];
+
+    if name == output_name {
+        return Some((idx, node));
+    }
+    let inner = match expr_arena.get(node) {
+        AExpr::Alias(inner, _) => *inner,
+        _ => node,
+    };
+    Some((
+        idx,
+        expr_arena.add(AExpr::Alias(inner, output_name.clone())),
+    ))
+}
+
+/// Substitute the expressions of the lower projection for the columns that the expressions of the
+/// upper projection refer to.
+fn compose(
+    upper_expr: &[Node],
+    schema: &Schema,
+    lower_expr: &[Node],
+    lower_input_schema: &Schema,
+    expr_arena: &mut Arena<AExpr>,
+) -> Option<Vec<Node>> {
+    // (name in the lower projection, expression without alias, number of uses)
+    let mut lower = Vec::with_capacity(lower_expr.len());
+    for node in lower_expr {
+        if !aexpr_is_elementwise(*node, expr_arena)
+            || !has_aexpr(*node, expr_arena, |e| matches!(e, AExpr::Column(_)))
+        {
+            return None;
+        }
+        let field = expr_arena
+            .get(*node)
+            .to_field(lower_input_schema, Context::Default, expr_arena)
+            .ok()?;
+        let inner = match expr_arena.get(*node) {
+            AExpr::Alias(inner, _) => *inner,
+            _ => *node,
+        };
+        lower.push((Arc::<str>::from(field.name().as_str()), inner, 0usize));
+    }
+
+    let mut merged = Vec::with_capacity(upper_expr.len());
+    for (node, output_name) in upper_expr.iter().zip(schema.iter_names()) {
+        // substitute in a copy, so that the nodes of the upper projection are not modified
+        let node = to_aexpr(node_to_expr(*node, expr_arena), expr_arena);
+        let columns = expr_arena
+            .iter(node)
+            .filter_map(|(node, e)| match e {
+                AExpr::Column(name) => Some((node, name.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (column, name) in columns {
+            let (_, inner, uses) = lower
+                .iter_mut()
+                .find(|(lower_name, _, _)| *lower_name == name)?;
+            *uses += 1;
+            let substituted = expr_arena.get(*inner).clone();
+            expr_arena.replace(column, substituted);
+        }
+        let node = match expr_arena.get(node) {
+            AExpr::Alias(_, _) => node,
+            _ => expr_arena.add(AExpr::Alias(node, Arc::from(output_name.as_str()))),
+        };
+        merged.push(node);
+    }
+
+    let computed_twice = lower
+        .iter()
+        .any(|(_, inner, uses)| *uses > 1 && !matches!(expr_arena.get(*inner), AExpr::Column(_)));
+    if computed_twice {
+        return None;
+    }
+    Some(merged)
+}
+
+impl OptimizationRule for MergeProjections {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        use ALogicalPlan::*;
+        let (input, upper_expr, schema) = match lp_arena.get(node) {
+            Projection {
+                input,
+                expr,
+                schema,
+            } => (*input, expr, schema.clone()),
+            _ => return None,
+        };
+        let (lower_input, lower_expr_nodes) = match lp_arena.get(input) {
+            Projection { input, expr, .. } => (*input, expr.clone()),
+            _ => return None,
+        };
+
+        let lower_input_schema = lp_arena.get(lower_input).schema(lp_arena);
+
+        // (name in the lower projection, name in the output)
+        let mut selected = Vec::with_capacity(upper_expr.len());
+        for node in upper_expr {
+            let column = match expr_arena.get(*node) {
+                AExpr::Column(name) => Some((name.clone(), name.clone())),
+                AExpr::Alias(inner, output_name) => match expr_arena.get(*inner) {
+                    AExpr::Column(name) => Some((name.clone(), output_name.clone())),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match column {
+                Some(column) => selected.push(column),
+                None => {
+                    let expr = compose(
+                        upper_expr,
+                        &schema,
+                        &lower_expr_nodes,
+                        lower_input_schema,
+                        expr_arena,
+                    )?;
+                    return Some(Projection {
+                        input: lower_input,
+                        expr,
+                        schema,
+                    });
+                }
+            }
+        }
+
+        let mut used = vec![false; lower_expr_nodes.len()];
+        let mut merged = Vec::with_capacity(selected.len());
+        for (name, output_name) in &selected {
+            let (idx, node) = lower_expr(
+                name,
+                output_name,
+                &lower_expr_nodes,
+                lower_input_schema,
+                expr_arena,
+            )?;
+            used[idx] = true;
+            merged.push(node);
+        }
+
+        let only_columns = lower_expr_nodes
+            .iter()
+            .all(|node| match expr_arena.get(*node) {
+                AExpr::Column(_) => true,
+                AExpr::Alias(inner, _) => matches!(expr_arena.get(*inner), AExpr::Column(_)),
+                _ => false,
+            });
+        if !only_columns && used.iter().any(|used| !used) {
+            return None;
+        }
+
+        Some(Projection {
+            input: lower_input,
+            expr: merged,
+            schema,
+        })
+    }
+}
@@ -4,10 +4,17 @@ use polars_core::{datatypes::PlHashMap, prelude::*};
 pub(crate) mod aggregate_pushdown;
 #[cfg(any(feature = "parquet", feature = "csv-file"))]
 pub(crate) mod aggregate_scan_projections;
+pub(crate) mod combine_filters;
+pub(crate) mod common_subplan;
+pub(crate) mod cse;
 pub(crate) mod drop_nulls;
 pub(crate) mod fast_projection;
+pub(crate) mod identity_projection;
+pub(crate) mod merge_projections;
 pub(crate) mod predicate_pushdown;
 pub(crate) mod projection_pushdown;
+pub(crate) mod redundant_sort;
+mod rewriter;
 pub(crate) mod simplify_expr;
 mod slice_pushdown_expr;
 pub mod slice_pushdown_lp;
@@ -16,6 +23,7 @@ pub(crate) mod type_coercion;
 
 use crate::prelude::stack_opt::OptimizationRule;
 
+pub use rewriter::{rewrite_exprs, rewrite_nodes, LogicalPlanRewriter};
 pub(crate) use slice_pushdown_lp::SlicePushDown;
 
 pub trait Optimize {
@@ -1,8 +1,13 @@
 mod utils;
 
+pub(super) use utils::is_expensive_predicate;
+
 use crate::logical_plan::{optimizer, Context};
 use crate::prelude::*;
-use crate::utils::{aexpr_to_root_names, aexprs_to_schema, check_input_node, has_aexpr};
+use crate::utils::{
+    aexpr_is_elementwise, aexpr_to_root_names, aexprs_to_schema, check_input_node, has_aexpr,
+    rename_aexpr_root_names,
+};
 use polars_core::datatypes::PlHashMap;
 use polars_core::prelude::*;
 use utils::*;
@@ -19,10 +24,13 @@ impl PredicatePushDown {
         expr_arena: &mut Arena<AExpr>,
     ) -> ALogicalPlan {
         if !local_predicates.is_empty() {
-            let predicate = combine_predicates(local_predicates.into_iter(), expr_arena);
+            let predicates = order_by_cost(local_predicates, expr_arena);
+            let (predicates, expensive) = split_expensive_predicates(predicates, expr_arena);
+            let predicate = combine_predicates(predicates.into_iter(), expr_arena);
             let input = lp_arena.add(lp);
 
-            ALogicalPlan::Selection { input, predicate }
+            let lp = ALogicalPlan::Selection { input, predicate };
+            self.optional_apply_predicate(lp, expensive, lp_arena, expr_arena)
         } else {
             lp
         }
@@ -75,6 +83,7 @@ impl PredicatePushDown {
             let lp = lp.with_exprs_and_input(projections, inputs);
             Ok(self.optional_apply_predicate(lp, local_predicates, lp_arena, expr_arena))
         } else {
+            let acc_predicates = sorted_predicates(acc_predicates);
             let mut local_predicates = Vec::with_capacity(acc_predicates.len());
 
             // determine new inputs by pushing down predicates
@@ -85,12 +94,12 @@ impl PredicatePushDown {
                     // it could be that this node just added the column where we base the predicate on
                     let input_schema = lp_arena.get(node).schema(lp_arena);
                     let mut pushdown_predicates = optimizer::init_hashmap();
-                    for (name, &predicate) in acc_predicates.iter() {
+                    for (name, predicate) in acc_predicates.iter().cloned() {
                         // we can pushdown the predicate
                         if check_input_node(predicate, input_schema, expr_arena) {
                             insert_and_combine_predicate(
                                 &mut pushdown_predicates,
-                                name.clone(),
+                                name,
                                 predicate,
                                 expr_arena,
                             )
@@ -136,7 +145,7 @@ impl PredicatePushDown {
         let lp = lp.with_exprs_and_input(exprs, new_inputs);
 
         // all predicates are done locally
-        let local_predicates = acc_predicates.values().copied().collect::<Vec<_>>();
+        let local_predicates = predicates_in_key_order(acc_predicates);
         Ok(self.optional_apply_predicate(lp, local_predicates, lp_arena, expr_arena))
     }
 
@@ -161,11 +170,35 @@ impl PredicatePushDown {
         use ALogicalPlan::*;
 
         match lp {
-            Selection { predicate, input } => {
-                let name = roots_to_key(&aexpr_to_root_names(predicate, expr_arena));
-                insert_and_combine_predicate(&mut acc_predicates, name, predicate, expr_arena);
-                let alp = lp_arena.take(input);
-                self.push_down(alp, acc_predicates, lp_arena, expr_arena)
+            Selection {
+                mut predicate,
+                mut input,
+            } => {
+                // stacked selections are accumulated in a loop, so that long chains don't
+                // overflow the stack
+                loop {
+                    let mut predicates = Vec::with_capacity(1);
+                    split_conjunctions(predicate, expr_arena, &mut predicates);
+                    for predicate in predicates {
+                        let name = roots_to_key(&aexpr_to_root_names(predicate, expr_arena));
+                        insert_and_combine_predicate(
+                            &mut acc_predicates,
+                            name,
+                            predicate,
+                            expr_arena,
+                        );
+                    }
+                    match lp_arena.take(input) {
+                        Selection {
+                            predicate: inner_predicate,
+                            input: inner,
+                        } => {
+                            predicate = inner_predicate;
+                            input = inner;
+                        }
+                        alp => return self.push_down(alp, acc_predicates, lp_arena, expr_arena),
+                    }
+                }
             }
             DataFrameScan {
                 df,
@@ -173,14 +206,15 @@ impl PredicatePushDown {
                 projection,
                 selection,
             } => {
-                let selection = predicate_at_scan(acc_predicates, selection, expr_arena);
+                let (selection, expensive) =
+                    selection_at_df_scan(acc_predicates, selection, expr_arena);
                 let lp = DataFrameScan {
                     df,
                     schema,
                     projection,
                     selection,
                 };
-                Ok(lp)
+                Ok(self.optional_apply_predicate(lp, expensive, lp_arena, expr_arena))
             }
 
             Melt {
@@ -189,7 +223,7 @@ impl PredicatePushDown {
                 schema,
             } => {
                 let variable_name = args.variable_name.as_deref().unwrap_or("variable");
-                let value_name = args.value_name.as_deref().unwrap_or("value_name");
+                let value_name = args.value_name.as_deref().unwrap_or("value");
 
                 // predicates that will be done at this level
                 let condition = |name: Arc<str>| {
@@ -290,43 +324,51 @@ impl PredicatePushDown {
                 };
                 Ok(lp)
             }
-            Explode { input, columns, schema } => {
+            Explode {
+                input,
+                columns,
+                schema,
+            } => {
                 let condition = |name: Arc<str>| columns.iter().any(|s| s.as_str() == &*name);
                 let local_predicates =
                     transfer_to_local(expr_arena, &mut acc_predicates, condition);
 
                 self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
-                let lp = Explode { input, columns, schema };
+                let lp = Explode {
+                    input,
+                    columns,
+                    schema,
+                };
                 Ok(self.optional_apply_predicate(lp, local_predicates, lp_arena, expr_arena))
             }
-            Distinct {
-                input,
-                options
-            } => {
-                // currently the distinct operation only keeps the first occurrences.
-                // this may have influence on the pushed down predicates. If the pushed down predicates
-                // contain a binary expression (thus depending on values in multiple columns)
-                // the final result may differ if it is pushed down.
-
-                let mut root_count = 0;
-
-                // if this condition is called more than once, its a binary or ternary operation.
-                let condition = |_| {
-                    if root_count == 0 {
-                        root_count += 1;
-                        false
+            Distinct { input, options } => {
+                // the rows that are kept have the same values in the subset columns as the
+                // duplicates that are dropped. So a predicate on the subset columns has the same
+                // result on all of them and can be done before the distinct operation.
+                // Without a subset all columns are compared.
+                let mut pushdown_predicates = optimizer::init_hashmap();
+                let mut local_predicates = Vec::with_capacity(acc_predicates.len());
+                for (name, predicate) in sorted_predicates(acc_predicates) {
+                    let on_subset = match &options.subset {
+                        Some(subset) => aexpr_to_root_names(predicate, expr_arena)
+                            .iter()
+                            .all(|root| subset.iter().any(|s| s.as_str() == &**root)),
+                        None => true,
+                    };
+                    if on_subset && aexpr_is_elementwise(predicate, expr_arena) {
+                        insert_and_combine_predicate(
+                            &mut pushdown_predicates,
+                            name,
+                            predicate,
+                            expr_arena,
+                        );
                     } else {
-                        true
+                        local_predicates.push(predicate)
                     }
-                };
-                let local_predicates =
-                    transfer_to_local(expr_arena, &mut acc_predicates, condition);
+                }
 
-                self.pushdown_and_assign(input, acc_predicates, lp_arena, expr_arena)?;
-                let lp = Distinct {
-                    input,
-                    options
-                };
+                self.pushdown_and_assign(input, pushdown_predicates, lp_arena, expr_arena)?;
+                let lp = Distinct { input, options };
                 Ok(self.optional_apply_predicate(lp, local_predicates, lp_arena, expr_arena))
             }
             Join {
@@ -340,11 +382,23 @@ impl PredicatePushDown {
                 let schema_left = lp_arena.get(input_left).schema(lp_arena);
                 let schema_right = lp_arena.get(input_right).schema(lp_arena);
 
+                // join keys with the same name on both sides refer to the same output column.
+                // The other columns of the right table that also exist in the left table get
+                // a suffix in the output, so a predicate on such a name belongs to the left table.
+                let shared_keys = left_on
+                    .iter()
+                    .zip(right_on.iter())
+                    .filter_map(|(l, r)| match (expr_arena.get(*l), expr_arena.get(*r)) {
+                        (AExpr::Column(l), AExpr::Column(r)) if l == r => Some(l.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
                 let mut pushdown_left = optimizer::init_hashmap();
                 let mut pushdown_right = optimizer::init_hashmap();
                 let mut local_predicates = Vec::with_capacity(acc_predicates.len());
 
-                for (_, predicate) in acc_predicates {
+                for predicate in predicates_in_key_order(acc_predicates) {
                     // unique and duplicated can be caused by joins
                     let matches =
                         |e: &AExpr| matches!(e, AExpr::IsUnique(_) | AExpr::Duplicated(_));
@@ -353,35 +407,50 @@ impl PredicatePushDown {
                         |e: &AExpr| matches!(e, AExpr::IsNull(_) | AExpr::IsNotNull(_));
                     if has_aexpr(predicate, expr_arena, matches)
                         // join might create null values.
-                        || has_aexpr(predicate, expr_arena, checks_nulls) && matches!(&options.how, JoinType::Left | JoinType::Outer | JoinType::Cross){
+                        || has_aexpr(predicate, expr_arena, checks_nulls) && matches!(&options.how, JoinType::Outer | JoinType::Cross)
+                    {
                         local_predicates.push(predicate);
                         continue;
                     }
-                    // these indicate to which tables we are going to push down the predicate
-                    let mut filter_left = false;
-                    let mut filter_right = false;
-
-                    // no else if. predicate can be in both tables.
-                    if check_input_node(predicate, schema_left, expr_arena) {
-                        let name = get_insertion_name(expr_arena, predicate, schema_left);
-                        insert_and_combine_predicate(
-                            &mut pushdown_left,
-                            name,
-                            predicate,
-                            expr_arena,
-                        );
-                        filter_left = true;
+                    // an outer join produces null values for the rows of both tables that have no
+                    // match, so filtering before the join changes the result.
+                    if options.how == JoinType::Outer {
+                        local_predicates.push(predicate);
+                        continue;
                     }
-                    if check_input_node(predicate, schema_right, expr_arena) {
-                        let name = get_insertion_name(expr_arena, predicate, schema_right);
-                        insert_and_combine_predicate(
-                            &mut pushdown_right,
-                            name,
+
+                    // a predicate on a suffixed column of the right table is pushed down with
+                    // the original name. For a left join these predicates stay local. Semi and
+                    // anti joins don't have columns of the right table.
+                    if options.how != JoinType::Left && !options.is_semi_anti() {
+                        if let Some(name) = suffixed_right_root(
                             predicate,
                             expr_arena,
-                        );
-                        filter_right = true;
+                            schema_left,
+                            schema_right,
+                            &shared_keys,
+                            options.suffix.as_ref(),
+                        ) {
+                            rename_aexpr_root_names(predicate, expr_arena, name.clone());
+                            insert_and_combine_predicate(
+                                &mut pushdown_right,
+                                name,
+                                predicate,
+                                expr_arena,
+                            );
+                            continue;
+                        }
                     }
+
+                    // these indicate to which tables we are going to push down the predicate
+                    let filter_left = check_input_node(predicate, schema_left, expr_arena);
+                    let filter_right = check_input_node(predicate, schema_right, expr_arena)
+                        && aexpr_to_root_names(predicate, expr_arena)
+                            .iter()
+                            .all(|name| {
+                                schema_left.get(name).is_none() || shared_keys.contains(name)
+                            });
+
                     match (filter_left, filter_right, &options.how) {
                         // if not pushed down on of the tables we have to do it locally.
                         (false, false, _) |
@@ -397,15 +466,34 @@ impl PredicatePushDown {
                         // business as usual
                         _ => {}
                     }
-                    // An outer join or left join may create null values.
-                    // we also do it local
-                    let matches = |e: &AExpr| matches!(e, AExpr::IsNotNull(_) | AExpr::IsNull(_));
-                    if (options.how == JoinType::Outer) | (options.how == JoinType::Left)
-                        && has_aexpr(predicate, expr_arena, matches)
+                    // A left join creates null values in the columns of the right table, so null
+                    // checks on those columns are done locally. The left table is not changed.
+                    if options.how == JoinType::Left
+                        && filter_right
+                        && has_aexpr(predicate, expr_arena, checks_nulls)
                     {
                         local_predicates.push(predicate);
                         continue;
                     }
+
+                    let name = roots_to_key(&aexpr_to_root_names(predicate, expr_arena));
+                    // no else if. predicate can be in both tables.
+                    if filter_left {
+                        insert_and_combine_predicate(
+                            &mut pushdown_left,
+                            name.clone(),
+                            predicate,
+                            expr_arena,
+                        );
+                    }
+                    if filter_right {
+                        insert_and_combine_predicate(
+                            &mut pushdown_right,
+                            name,
+                            predicate,
+                            expr_arena,
+                        );
+                    }
                 }
 
                 self.pushdown_and_assign(input_left, pushdown_left, lp_arena, expr_arena)?;
@@ -424,9 +512,11 @@ impl PredicatePushDown {
 
             lp @ Udf { .. } => {
                 if let ALogicalPlan::Udf {
-                    options: LogicalPlanUdfOptions {
-                        predicate_pd: true, ..
-                    }, ..
+                    options:
+                        LogicalPlanUdfOptions {
+                            predicate_pd: true, ..
+                        },
+                    ..
                 } = lp
                 {
                     self.pushdown_and_continue(lp, acc_predicates, lp_arena, expr_arena, false)
@@ -435,17 +525,74 @@ impl PredicatePushDown {
                 }
             }
             // Pushed down passed these nodes
-            lp @ Cache { .. } | lp @ Union { .. } | lp @ Sort { .. } => {
+            lp @ Union { .. } | lp @ Sort { .. } => {
                 self.pushdown_and_continue(lp, acc_predicates, lp_arena, expr_arena, false)
             }
-            lp @ HStack {..} | lp @ Projection {..} => {
+            lp @ HStack { .. } | lp @ Projection { .. } => {
                 self.pushdown_and_continue(lp, acc_predicates, lp_arena, expr_arena, true)
             }
+            Aggregate {
+                input,
+                keys,
+                aggs,
+                schema,
+                apply,
+                maintain_order,
+                options,
+            } => {
+                // Predicates that only refer to the group keys remove complete groups and can
+                // be applied before the aggregation. Predicates on the aggregated values need
+                // all rows, so those are applied afterwards.
+                let key_names = if apply.is_none()
+                    && options.dynamic.is_none()
+                    && options.rolling.is_none()
+                    && options.slice.is_none()
+                {
+                    keys.iter()
+                        .filter_map(|node| match expr_arena.get(*node) {
+                            AExpr::Column(name) => Some(name.clone()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                } else {
+                    vec![]
+                };
+
+                let mut pushdown_predicates = optimizer::init_hashmap();
+                let mut local_predicates = Vec::with_capacity(acc_predicates.len());
+                for (name, predicate) in sorted_predicates(acc_predicates) {
+                    let roots = aexpr_to_root_names(predicate, expr_arena);
+                    if !roots.is_empty()
+                        && roots.iter().all(|root| key_names.contains(root))
+                        && aexpr_is_elementwise(predicate, expr_arena)
+                    {
+                        insert_and_combine_predicate(
+                            &mut pushdown_predicates,
+                            name,
+                            predicate,
+                            expr_arena,
+                        );
+                    } else {
+                        local_predicates.push(predicate)
+                    }
+                }
+
+                self.pushdown_and_assign(input, pushdown_predicates, lp_arena, expr_arena)?;
+                let lp = Aggregate {
+                    input,
+                    keys,
+                    aggs,
+                    schema,
+                    apply,
+                    maintain_order,
+                    options,
+                };
+                Ok(self.optional_apply_predicate(lp, local_predicates, lp_arena, expr_arena))
+            }
             // NOT Pushed down passed these nodes
             // predicates influence slice sizes
-            lp @ Slice { .. }
-            // dont push down predicates. An aggregation needs all rows
-            | lp @ Aggregate {..} => {
+            // a cache may be shared with other branches of the plan
+            lp @ Slice { .. } | lp @ Cache { .. } => {
                 self.no_pushdown_restart_opt(lp, acc_predicates, lp_arena, expr_arena)
             }
         }
@@ -486,4 +633,81 @@ mod test {
             format!("{:?}", predicate_expr.and(lit(true)))
         );
     }
+
+    #[test]
+    fn test_insert_duplicate_predicate() {
+        let mut acc_predicates = PlHashMap::with_capacity(32);
+        let mut expr_arena = Arena::new();
+
+        for predicate_expr in [
+            col("foo").gt(lit(1)),
+            col("foo").lt(lit(5)),
+            col("foo").gt(lit(1)),
+        ] {
+            let predicate = to_aexpr(predicate_expr, &mut expr_arena);
+            insert_and_combine_predicate(
+                &mut acc_predicates,
+                Arc::from("foo"),
+                predicate,
+                &mut expr_arena,
+            );
+        }
+        let root = *acc_predicates.get("foo").unwrap();
+        let expr = node_to_expr(root, &expr_arena);
+        assert_eq!(
+            format!("{:?}", &expr),
+            format!(
+                "{:?}",
+                col("foo")
+                    .lt(lit(5))
+                    .and(col("foo").gt(lit(1)).and(lit(true)))
+            )
+        );
+    }
+
+    #[test]
+    fn test_roots_to_key() {
+        let key = |names: &[&str]| {
+            let roots = names
+                .iter()
+                .map(|name| Arc::from(*name))
+                .collect::<Vec<_>>();
+            roots_to_key(&roots)
+        };
+        assert_eq!(key(&["b", "a"]), key(&["a", "b", "a"]));
+        assert_eq!(key(&["a", "a"]), key(&["a"]));
+        assert_ne!(key(&["a", "bc"]), key(&["ab", "c"]));
+        assert_ne!(key(&["a", "b"]), key(&["ab"]));
+    }
+
+    #[test]
+    fn test_order_by_cost() {
+        let mut expr_arena = Arena::new();
+        let predicate = to_aexpr(
+            col("name")
+                .eq(lit("foo"))
+                .and(col("a").gt(lit(1)))
+                .and(col("b").is_null()),
+            &mut expr_arena,
+        );
+
+        let predicates = order_by_cost(vec![predicate], &expr_arena);
+        let exprs = predicates
+            .iter()
+            .map(|node| node_to_expr(*node, &expr_arena))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exprs,
+            &[
+                col("b").is_null(),
+                col("a").gt(lit(1)),
+                col("name").eq(lit("foo"))
+            ]
+        );
+
+        // the string comparison is only evaluated on the rows that pass the others
+        let (first, expensive) = split_expensive_predicates(predicates.clone(), &expr_arena);
+        assert_eq!(first, &predicates[..2]);
+        assert_eq!(expensive, &predicates[2..]);
+    }
 }
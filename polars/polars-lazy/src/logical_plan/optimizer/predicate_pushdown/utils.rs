@@ -1,6 +1,9 @@
-use crate::logical_plan::Context;
+use crate::logical_plan::conversion::node_to_expr;
+use crate::prelude::iterator::ArenaExprIter;
 use crate::prelude::*;
-use crate::utils::{aexpr_to_root_names, check_input_node, has_aexpr, rename_aexpr_root_names};
+use crate::utils::{
+    aexpr_is_elementwise, aexpr_to_root_names, check_input_node, has_aexpr, rename_aexpr_root_names,
+};
 use polars_core::datatypes::PlHashMap;
 use polars_core::prelude::*;
 
@@ -18,7 +21,8 @@ impl Dsl for Node {
     }
 }
 
-/// Don't overwrite predicates but combine them.
+/// Don't overwrite predicates but combine them. A predicate that is equal to one that is already
+/// combined under the same key is not added again.
 pub(super) fn insert_and_combine_predicate(
     acc_predicates: &mut PlHashMap<Arc<str>, Node>,
     name: Arc<str>,
@@ -29,6 +33,16 @@ pub(super) fn insert_and_combine_predicate(
         .entry(name)
         .or_insert_with(|| arena.add(AExpr::Literal(LiteralValue::Boolean(true))));
 
+    let mut existing = Vec::new();
+    split_conjunctions(*existing_predicate, arena, &mut existing);
+    let expr = node_to_expr(predicate, arena);
+    if existing
+        .iter()
+        .any(|node| node_to_expr(*node, arena) == expr)
+    {
+        return;
+    }
+
     let node = arena.add(AExpr::BinaryExpr {
         left: predicate,
         op: Operator::And,
@@ -38,6 +52,22 @@ pub(super) fn insert_and_combine_predicate(
     *existing_predicate = node;
 }
 
+/// Split a predicate on its top-level `AND` operators, so that the parts can be pushed down
+/// independently. `a AND (b OR c)` splits into `a` and `b OR c`.
+pub(super) fn split_conjunctions(node: Node, arena: &Arena<AExpr>, out: &mut Vec<Node>) {
+    match arena.get(node) {
+        AExpr::BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        } => {
+            split_conjunctions(*left, arena, out);
+            split_conjunctions(*right, arena, out);
+        }
+        _ => out.push(node),
+    }
+}
+
 pub(super) fn combine_predicates<I>(iter: I, arena: &mut Arena<AExpr>) -> Node
 where
     I: Iterator<Item = Node>,
@@ -56,51 +86,164 @@ where
     single_pred.expect("an empty iterator was passed")
 }
 
+/// Take the predicates with their keys, ordered by their key. The iteration order of the hashmap
+/// is not deterministic, so we sort to ensure the same query always leads to the same plan.
+pub(super) fn sorted_predicates(
+    acc_predicates: PlHashMap<Arc<str>, Node>,
+) -> Vec<(Arc<str>, Node)> {
+    let mut predicates = acc_predicates.into_iter().collect::<Vec<_>>();
+    predicates.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    predicates
+}
+
+/// Take the predicates ordered by their key.
+pub(super) fn predicates_in_key_order(acc_predicates: PlHashMap<Arc<str>, Node>) -> Vec<Node> {
+    sorted_predicates(acc_predicates)
+        .into_iter()
+        .map(|t| t.1)
+        .collect()
+}
+
+/// Predicates of this cost or more are evaluated on the rows that pass the cheaper predicates, if
+/// they are element wise.
+const EXPENSIVE_PREDICATE: u8 = 4;
+
+/// A static estimate of the cost of evaluating a predicate. Null checks are cheaper than
+/// comparisons, comparisons are cheaper than arithmetic, and predicates that touch strings or
+/// call functions are the most expensive.
+pub(super) fn predicate_cost(node: Node, arena: &Arena<AExpr>) -> u8 {
+    arena
+        .iter(node)
+        .map(|(_, e)| match e {
+            AExpr::Literal(LiteralValue::Utf8(_))
+            | AExpr::Cast {
+                data_type: DataType::Utf8,
+                ..
+            } => EXPENSIVE_PREDICATE,
+            AExpr::Column(_) | AExpr::Literal(_) | AExpr::Alias(_, _) => 0,
+            AExpr::IsNull(_) | AExpr::IsNotNull(_) | AExpr::Not(_) => 1,
+            AExpr::BinaryExpr { op, .. }
+                if op.is_comparison() || matches!(op, Operator::And | Operator::Or) =>
+            {
+                2
+            }
+            AExpr::BinaryExpr { .. } | AExpr::Cast { .. } | AExpr::Ternary { .. } => 3,
+            _ => EXPENSIVE_PREDICATE + 1,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Split the predicates on their conjunctions and order the parts by their cost, so that cheap
+/// predicates are evaluated first. The sort is stable, so parts of the same cost keep their order.
+/// The `true` literals that [`insert_and_combine_predicate`] starts with are dropped.
+pub(super) fn order_by_cost(predicates: Vec<Node>, arena: &Arena<AExpr>) -> Vec<Node> {
+    let mut parts = Vec::with_capacity(predicates.len());
+    for node in &predicates {
+        split_conjunctions(*node, arena, &mut parts);
+    }
+    parts.retain(|node| {
+        !matches!(
+            arena.get(*node),
+            AExpr::Literal(LiteralValue::Boolean(true))
+        )
+    });
+    if parts.is_empty() {
+        return predicates;
+    }
+    parts.sort_by_key(|node| predicate_cost(*node, arena));
+    parts
+}
+
+/// Check if a predicate is expensive and element wise, so that it can be evaluated on the rows
+/// that pass cheaper predicates.
+pub(crate) fn is_expensive_predicate(node: Node, arena: &Arena<AExpr>) -> bool {
+    predicate_cost(node, arena) >= EXPENSIVE_PREDICATE && aexpr_is_elementwise(node, arena)
+}
+
+/// Split predicates that are ordered by [`order_by_cost`] in the predicates that are evaluated
+/// first, and the expensive element wise predicates that only need to be evaluated on the rows
+/// that pass the first ones.
+pub(super) fn split_expensive_predicates(
+    predicates: Vec<Node>,
+    arena: &Arena<AExpr>,
+) -> (Vec<Node>, Vec<Node>) {
+    let (expensive, first): (Vec<_>, Vec<_>) = predicates
+        .into_iter()
+        .partition(|node| is_expensive_predicate(*node, arena));
+    if first.is_empty() {
+        (expensive, vec![])
+    } else {
+        (first, expensive)
+    }
+}
+
+/// Combine the accumulated predicates with the predicate of a file scan. The predicate is kept
+/// whole, as the readers apply it while reading and the parquet reader uses it to skip row groups.
 pub(super) fn predicate_at_scan(
     acc_predicates: PlHashMap<Arc<str>, Node>,
     predicate: Option<Node>,
     expr_arena: &mut Arena<AExpr>,
 ) -> Option<Node> {
     if !acc_predicates.is_empty() {
-        let mut new_predicate =
-            combine_predicates(acc_predicates.into_iter().map(|t| t.1), expr_arena);
+        let predicates = order_by_cost(predicates_in_key_order(acc_predicates), expr_arena);
+        let mut new_predicate = combine_predicates(predicates.into_iter(), expr_arena);
         if let Some(pred) = predicate {
             new_predicate = new_predicate.and(pred, expr_arena)
         }
         Some(new_predicate)
     } else {
-        None
+        predicate
+    }
+}
+
+/// Combine the accumulated predicates with the selection of an in memory scan. The expensive
+/// predicates are returned separately, so that they are only evaluated on the selected rows.
+pub(super) fn selection_at_df_scan(
+    acc_predicates: PlHashMap<Arc<str>, Node>,
+    selection: Option<Node>,
+    expr_arena: &mut Arena<AExpr>,
+) -> (Option<Node>, Vec<Node>) {
+    if !acc_predicates.is_empty() {
+        let predicates = order_by_cost(predicates_in_key_order(acc_predicates), expr_arena);
+        let (predicates, expensive) = split_expensive_predicates(predicates, expr_arena);
+        let mut new_selection = combine_predicates(predicates.into_iter(), expr_arena);
+        if let Some(pred) = selection {
+            new_selection = new_selection.and(pred, expr_arena)
+        }
+        (Some(new_selection), expensive)
+    } else {
+        (selection, vec![])
     }
 }
 
-/// Determine the hashmap key by combining all the root column names of a predicate
+const KEY_SEPARATOR: char = '\0';
+
+/// Determine the hashmap key by combining all the root column names of a predicate.
+/// The names are sorted and deduplicated, so that predicates on the same set of columns
+/// get the same key, regardless of the order in which the columns are referred to.
+///
+/// The names of a key of multiple columns are each prefixed with a nul character, so that it
+/// cannot collide with the key of another set of columns or with the name of a single column.
 pub(super) fn roots_to_key(roots: &[Arc<str>]) -> Arc<str> {
     if roots.len() == 1 {
         roots[0].clone()
     } else {
+        let mut roots = roots.to_vec();
+        roots.sort_unstable();
+        roots.dedup();
+        if roots.len() == 1 {
+            return roots.pop().unwrap();
+        }
         let mut new = String::with_capacity(32 * roots.len());
         for name in roots {
-            new.push_str(name);
+            new.push(KEY_SEPARATOR);
+            new.push_str(&name);
         }
         Arc::from(new)
     }
 }
 
-pub(super) fn get_insertion_name(
-    expr_arena: &Arena<AExpr>,
-    predicate: Node,
-    schema: &Schema,
-) -> Arc<str> {
-    Arc::from(
-        expr_arena
-            .get(predicate)
-            .to_field(schema, Context::Default, expr_arena)
-            .unwrap()
-            .name()
-            .as_ref(),
-    )
-}
-
 /// Some predicates should not pass a projection if they would influence results of other columns.
 /// For instance shifts | sorts results are influenced by a filter so we do all predicates before the shift | sort
 /// The rule of thumb is any operation that changes the order of a column w/r/t other columns should be a
@@ -164,6 +307,37 @@ pub(super) fn predicate_column_is_pushdown_boundary(node: Node, expr_arena: &Are
     has_aexpr(node, expr_arena, matches)
 }
 
+/// A column of the right table that also exists in the left table gets a suffix in the output of
+/// a join. If the predicate only refers to such a column, this returns the name of that column in
+/// the right table.
+pub(super) fn suffixed_right_root(
+    predicate: Node,
+    expr_arena: &Arena<AExpr>,
+    schema_left: &Schema,
+    schema_right: &Schema,
+    shared_keys: &[Arc<str>],
+    suffix: &str,
+) -> Option<Arc<str>> {
+    let roots = aexpr_to_root_names(predicate, expr_arena);
+    let root = roots.first()?;
+    if roots.iter().any(|name| name != root) || !root.ends_with(suffix) {
+        return None;
+    }
+    // a column that is really named with the suffix
+    if schema_left.get(root).is_some() || schema_right.get(root).is_some() {
+        return None;
+    }
+    let name = &root[..root.len() - suffix.len()];
+    if schema_left.get(name).is_some()
+        && schema_right.get(name).is_some()
+        && !shared_keys.iter().any(|key| &**key == name)
+    {
+        Some(Arc::from(name))
+    } else {
+        None
+    }
+}
+
 /// Implementation for both Hstack and Projection
 pub(super) fn rewrite_projection_node(
     expr_arena: &mut Arena<AExpr>,
@@ -185,7 +359,7 @@ where
         let projection_roots = aexpr_to_root_names(*projection_node, expr_arena);
         {
             let projection_aexpr = expr_arena.get(*projection_node);
-            if let AExpr::Alias(_, name) = projection_aexpr {
+            if let AExpr::Alias(input_node, name) = projection_aexpr {
                 // if this alias refers to one of the predicates in the upper nodes
                 // we rename the column of the predicate before we push it downwards.
 
@@ -194,7 +368,10 @@ where
                         local_predicates.push(predicate);
                         continue;
                     }
-                    if projection_roots.len() == 1 {
+                    // only a renamed column has the same values as its root column,
+                    // a predicate on a computed value must be done after the projection.
+                    let is_renamed_column = matches!(expr_arena.get(*input_node), AExpr::Column(_));
+                    if is_renamed_column && projection_roots.len() == 1 {
                         // we were able to rename the alias column with the root column name
                         // before pushing down the predicate
                         rename_aexpr_root_names(predicate, expr_arena, projection_roots[0].clone());
@@ -244,6 +421,9 @@ where
                 }
             })
             .collect::<Vec<_>>();
+        // remove in key order, so that the local predicates are combined deterministically
+        let mut to_local = to_local;
+        to_local.sort_unstable();
 
         for name in to_local {
             let local = acc_predicates.remove(&name).unwrap();
@@ -304,6 +484,7 @@ where
             }
         }
     }
+    remove_keys.sort_unstable();
     let mut local_predicates = Vec::with_capacity(remove_keys.len());
     for key in remove_keys {
         if let Some(pred) = acc_predicates.remove(&*key) {
@@ -1,9 +1,10 @@
 use crate::logical_plan::Context;
+use crate::prelude::identity_projection::projects_all_columns;
 use crate::prelude::iterator::ArenaExprIter;
 use crate::prelude::*;
 use crate::utils::{
-    aexpr_assign_renamed_root, aexpr_to_root_names, aexpr_to_root_nodes, check_input_node,
-    has_aexpr,
+    aexpr_assign_renamed_root, aexpr_to_root_column_name, aexpr_to_root_names, aexpr_to_root_nodes,
+    check_input_node, has_aexpr,
 };
 use polars_core::{datatypes::PlHashSet, prelude::*};
 
@@ -77,6 +78,19 @@ fn add_expr_to_accumulated(
     }
 }
 
+/// Get the column of `schema` that is the cheapest to read: the first boolean column, otherwise
+/// the first numeric column, otherwise the first column.
+fn cheapest_column(schema: &Schema) -> Option<Arc<str>> {
+    schema
+        .iter()
+        .min_by_key(|(_, dtype)| match dtype {
+            DataType::Boolean => 0,
+            dtype if dtype.is_numeric() => 1,
+            _ => 2,
+        })
+        .map(|(name, _)| Arc::from(name.as_str()))
+}
+
 fn add_str_to_accumulated(
     name: &str,
     acc_projections: &mut Vec<Node>,
@@ -99,12 +113,14 @@ fn update_scan_schema(
     // sorting parsers: csv and ipc,
     // non-sorting: parquet
     sort_projections: bool,
-) -> Schema {
+) -> Result<Schema> {
     let mut new_schema = Schema::with_capacity(acc_projections.len());
     let mut new_cols = Vec::with_capacity(acc_projections.len());
     for node in acc_projections.iter() {
         for name in aexpr_to_root_names(*node, expr_arena) {
-            let item = schema.get_full(&*name).unwrap();
+            let item = schema
+                .get_full(&*name)
+                .ok_or_else(|| PolarsError::NotFound(name.to_string()))?;
             new_cols.push(item);
         }
     }
@@ -115,7 +131,7 @@ fn update_scan_schema(
     for item in new_cols {
         new_schema.with_column(item.1.clone(), item.2.clone())
     }
-    new_schema
+    Ok(new_schema)
 }
 
 pub(crate) struct ProjectionPushDown {}
@@ -133,40 +149,6 @@ impl ProjectionPushDown {
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn join_push_down(
-        &self,
-        schema_left: &Schema,
-        schema_right: &Schema,
-        proj: Node,
-        pushdown_left: &mut Vec<Node>,
-        pushdown_right: &mut Vec<Node>,
-        names_left: &mut PlHashSet<Arc<str>>,
-        names_right: &mut PlHashSet<Arc<str>>,
-        expr_arena: &mut Arena<AExpr>,
-    ) -> bool {
-        let mut pushed_at_least_one = false;
-        let names = aexpr_to_root_names(proj, expr_arena);
-        let root_projections = aexpr_to_root_nodes(proj, expr_arena);
-
-        for (name, root_projection) in names.into_iter().zip(root_projections) {
-            if check_input_node(root_projection, schema_left, expr_arena)
-                && names_left.insert(name.clone())
-            {
-                pushdown_left.push(proj);
-                pushed_at_least_one = true;
-            }
-            if check_input_node(root_projection, schema_right, expr_arena)
-                && names_right.insert(name)
-            {
-                pushdown_right.push(proj);
-                pushed_at_least_one = true;
-            }
-        }
-
-        pushed_at_least_one
-    }
-
     /// This pushes down current node and assigns the result to this node.
     fn pushdown_and_assign(
         &self,
@@ -277,6 +259,19 @@ impl ProjectionPushDown {
                         expr_arena,
                     );
                 }
+                // a projection that doesn't use any column, such as `count()`, only needs the
+                // height of its input, so we push down a single column instead of all of them.
+                if acc_projections.is_empty() {
+                    if let Some(name) = cheapest_column(lp_arena.get(input).schema(lp_arena)) {
+                        let node = expr_arena.add(AExpr::Column(name));
+                        add_expr_to_accumulated(
+                            node,
+                            &mut acc_projections,
+                            &mut projected_names,
+                            expr_arena,
+                        );
+                    }
+                }
 
                 self.pushdown_and_assign(
                     input,
@@ -342,17 +337,25 @@ impl ProjectionPushDown {
                 df,
                 mut schema,
                 selection,
-                ..
+                mut projection,
             } => {
-                let mut projection = None;
+                // a scan that was already projected keeps its projection if all its columns are
+                // needed. Otherwise the columns are selected from the schema of the frame.
                 if !acc_projections.is_empty() {
-                    schema = Arc::new(update_scan_schema(
-                        &acc_projections,
-                        expr_arena,
-                        &*schema,
-                        false,
-                    ));
-                    projection = Some(acc_projections);
+                    let df_schema = df.schema();
+                    // selecting all the columns in the order of the frame only clones the frame
+                    if projects_all_columns(&acc_projections, &df_schema, expr_arena) {
+                        schema = Arc::new(df_schema);
+                        projection = None;
+                    } else {
+                        schema = Arc::new(update_scan_schema(
+                            &acc_projections,
+                            expr_arena,
+                            &df_schema,
+                            false,
+                        )?);
+                        projection = Some(acc_projections);
+                    }
                 }
                 let lp = DataFrameScan {
                     df,
@@ -380,7 +383,7 @@ impl ProjectionPushDown {
                         expr_arena,
                         &*schema,
                         true,
-                    )))
+                    )?))
                 };
                 options.with_columns = with_columns;
 
@@ -413,7 +416,7 @@ impl ProjectionPushDown {
                         expr_arena,
                         &*schema,
                         false,
-                    )))
+                    )?))
                 };
                 options.with_columns = with_columns;
 
@@ -446,7 +449,7 @@ impl ProjectionPushDown {
                         expr_arena,
                         &*schema,
                         true,
-                    )))
+                    )?))
                 };
 
                 let lp = CsvScan {
@@ -464,7 +467,17 @@ impl ProjectionPushDown {
                 by_column,
                 args,
             } => {
+                // the projections above the sort, if it adds columns that are only used to sort
+                let mut local_projections = vec![];
                 if !acc_projections.is_empty() {
+                    let sort_columns_projected = by_column.iter().all(|node| {
+                        aexpr_to_root_names(*node, expr_arena)
+                            .iter()
+                            .all(|name| projected_names.contains(name))
+                    });
+                    if !sort_columns_projected {
+                        local_projections = acc_projections.clone();
+                    }
                     // Make sure that the column(s) used for the sort is projected
                     by_column.iter().for_each(|node| {
                         aexpr_to_root_nodes(*node, expr_arena)
@@ -488,11 +501,13 @@ impl ProjectionPushDown {
                     lp_arena,
                     expr_arena,
                 )?;
-                Ok(Sort {
+                let lp = Sort {
                     input,
                     by_column,
                     args,
-                })
+                };
+                let builder = ALogicalPlanBuilder::from_lp(lp, expr_arena, lp_arena);
+                Ok(self.finish_node(local_projections, builder))
             }
             Explode {
                 input,
@@ -532,6 +547,17 @@ impl ProjectionPushDown {
                             expr_arena,
                         )
                     })
+                } else {
+                    // without a subset the rows are compared on all columns
+                    let input_schema = lp_arena.get(input).schema(lp_arena);
+                    for (name, _) in input_schema.iter() {
+                        add_str_to_accumulated(
+                            name,
+                            &mut acc_projections,
+                            &mut projected_names,
+                            expr_arena,
+                        )
+                    }
                 }
 
                 self.pushdown_and_assign(
@@ -545,17 +571,27 @@ impl ProjectionPushDown {
                 Ok(Distinct { input, options })
             }
             Selection { predicate, input } => {
-                if !acc_projections.is_empty() {
-                    // make sure that the filter column is projected
-                    add_expr_to_accumulated(
-                        predicate,
-                        &mut acc_projections,
-                        &mut projected_names,
-                        expr_arena,
-                    );
+                // stacked selections are visited in a loop, so that long chains don't overflow
+                // the stack. Only the input below the last selection is pushed down into.
+                let mut current = (predicate, input);
+                let bottom = loop {
+                    let (predicate, input) = current;
+                    if !acc_projections.is_empty() {
+                        // make sure that the filter column is projected
+                        add_expr_to_accumulated(
+                            predicate,
+                            &mut acc_projections,
+                            &mut projected_names,
+                            expr_arena,
+                        );
+                    };
+                    match lp_arena.get(input) {
+                        Selection { predicate, input } => current = (*predicate, *input),
+                        _ => break input,
+                    }
                 };
                 self.pushdown_and_assign(
-                    input,
+                    bottom,
                     acc_projections,
                     projected_names,
                     projections_seen,
@@ -575,6 +611,21 @@ impl ProjectionPushDown {
                     local_projections.extend_from_slice(&acc_projections);
                 }
 
+                // without value columns, all the columns that are not an id are melted, so
+                // none of the input columns can be dropped
+                if args.value_vars.is_empty() {
+                    self.pushdown_and_assign(
+                        input,
+                        init_vec(),
+                        init_set(),
+                        projections_seen,
+                        lp_arena,
+                        expr_arena,
+                    )?;
+                    let builder = ALogicalPlanBuilder::new(input, expr_arena, lp_arena).melt(args);
+                    return Ok(self.finish_node(local_projections, builder));
+                }
+
                 // make sure that the requested columns are projected
                 args.id_vars.iter().for_each(|name| {
                     add_str_to_accumulated(
@@ -630,6 +681,30 @@ impl ProjectionPushDown {
                     let builder = ALogicalPlanBuilder::new(input, expr_arena, lp_arena);
                     Ok(self.finish_node(acc_projections, builder))
                 } else {
+                    // only the aggregations that are projected in the upper nodes are computed.
+                    // if none of them are, we keep them all so that the groupby keeps its shape.
+                    let aggs = if acc_projections.is_empty() {
+                        aggs
+                    } else {
+                        let input_schema = lp_arena.get(input).schema(lp_arena);
+                        let projected_aggs = aggs
+                            .iter()
+                            .copied()
+                            .filter(|agg| {
+                                expr_arena
+                                    .get(*agg)
+                                    .to_field(input_schema, Context::Aggregation, expr_arena)
+                                    .map(|field| projected_names.contains(field.name().as_str()))
+                                    .unwrap_or(true)
+                            })
+                            .collect::<Vec<_>>();
+                        if projected_aggs.is_empty() {
+                            aggs
+                        } else {
+                            projected_aggs
+                        }
+                    };
+
                     // todo! remove unnecessary vec alloc.
                     let (mut acc_projections, _local_projections, mut names) =
                         split_acc_projections(
@@ -693,6 +768,9 @@ impl ProjectionPushDown {
                 let mut names_left = init_set();
                 let mut names_right = init_set();
                 let mut local_projection = init_vec();
+                // the output columns of the join with the input they come from and their name in
+                // that input
+                let mut join_columns = PlHashMap::new();
 
                 // if there are no projections we don't have to do anything (all columns are projected)
                 // otherwise we build local projections to sort out proper column names due to the
@@ -728,56 +806,41 @@ impl ProjectionPushDown {
                         // we don't add right column names to local_projection as they are removed
                     }
 
+                    // The column of the left table keeps its name, the column of the right table
+                    // gets the suffix if the left table has a column with the same name.
+                    // Thus joining two tables with both a foo column leads to ["foo", "foo_right"].
+                    let right_names = join_key_names(&right_on, expr_arena);
+                    join_columns =
+                        det_join_columns(schema_left, schema_right, &right_names, &options)
+                            .into_iter()
+                            .map(|column| {
+                                (column.name, (column.side, Arc::from(column.input_name)))
+                            })
+                            .collect::<PlHashMap<String, (JoinSide, Arc<str>)>>();
+
                     for proj in acc_projections {
-                        let mut add_local = true;
-
-                        // if it is an alias we want to project the root column name downwards
-                        // but we don't want to project it a this level, otherwise we project both
-                        // the root and the alias, hence add_local = false.
-                        if let AExpr::Alias(expr, name) = expr_arena.get(proj).clone() {
-                            for root_name in aexpr_to_root_names(expr, expr_arena) {
-                                let node = expr_arena.add(AExpr::Column(root_name));
-                                let proj = expr_arena.add(AExpr::Alias(node, name.clone()));
-                                local_projection.push(proj)
+                        // An aliased projection is pushed down by its root columns and aliased
+                        // in the local projection, so that the alias composes with the suffix
+                        // handling below.
+                        let mut add_local = matches!(expr_arena.get(proj), AExpr::Alias(_, _));
+
+                        for root in aexpr_to_root_nodes(proj, expr_arena) {
+                            let name = aexpr_to_root_column_name(root, expr_arena)?;
+                            let (side, input_name) = match join_columns.get(&*name) {
+                                Some(column) => column,
+                                None => continue,
+                            };
+                            let (pushdown, names) = match side {
+                                JoinSide::Left => (&mut pushdown_left, &mut names_left),
+                                JoinSide::Right => (&mut pushdown_right, &mut names_right),
+                            };
+                            if names.insert(input_name.clone()) {
+                                pushdown.push(expr_arena.add(AExpr::Column(input_name.clone())));
                             }
-                            // now we don
-                            add_local = false;
+                            add_local = true;
                         }
 
-                        // Path for renamed columns due to the join. The column name of the left table
-                        // stays as is, the column of the right will have the "_right" suffix.
-                        // Thus joining two tables with both a foo column leads to ["foo", "foo_right"]
-                        if !self.join_push_down(
-                            schema_left,
-                            schema_right,
-                            proj,
-                            &mut pushdown_left,
-                            &mut pushdown_right,
-                            &mut names_left,
-                            &mut names_right,
-                            expr_arena,
-                        ) {
-                            // Column name of the projection without any alias.
-                            let root_column_name =
-                                aexpr_to_root_names(proj, expr_arena).pop().unwrap();
-
-                            let suffix = options.suffix.as_ref();
-                            // If _right suffix exists we need to push a projection down without this
-                            // suffix.
-                            if root_column_name.ends_with(suffix) {
-                                // downwards name is the name without the _right i.e. "foo".
-                                let (downwards_name, _) = root_column_name
-                                    .split_at(root_column_name.len() - suffix.len());
-
-                                let downwards_name_column =
-                                    expr_arena.add(AExpr::Column(Arc::from(downwards_name)));
-                                // project downwards and locally immediately alias to prevent wrong projections
-                                if names_right.insert(Arc::from(downwards_name)) {
-                                    pushdown_right.push(downwards_name_column);
-                                }
-                                local_projection.push(proj);
-                            }
-                        } else if add_local {
+                        if add_local {
                             // always also do the projection locally, because the join columns may not be
                             // included in the projection.
                             // for instance:
@@ -822,8 +885,6 @@ impl ProjectionPushDown {
                 // we have after the join. If we cannot then we modify the projection:
                 //
                 // col("foo_right")  to col("foo").alias("foo_right")
-                let suffix = options.suffix.clone();
-
                 let alp = ALogicalPlanBuilder::new(input_left, expr_arena, lp_arena)
                     .join(input_right, left_on, right_on, options)
                     .build();
@@ -831,15 +892,18 @@ impl ProjectionPushDown {
 
                 for proj in &mut local_projection {
                     for name in aexpr_to_root_names(*proj, expr_arena) {
-                        if name.contains(suffix.as_ref()) && schema_after_join.get(&*name).is_none()
-                        {
-                            let new_name = &name.as_ref()[..name.len() - suffix.len()];
-
+                        if schema_after_join.get(&*name).is_some() {
+                            continue;
+                        }
+                        if let Some((JoinSide::Right, new_name)) = join_columns.get(&*name) {
                             let renamed =
                                 aexpr_assign_renamed_root(*proj, expr_arena, &*name, new_name);
 
-                            let aliased = expr_arena.add(AExpr::Alias(renamed, name));
-                            *proj = aliased;
+                            // keep the output name of the projection
+                            *proj = match expr_arena.get(renamed) {
+                                AExpr::Alias(_, _) => renamed,
+                                _ => expr_arena.add(AExpr::Alias(renamed, name)),
+                            };
                         }
                     }
                 }
@@ -847,10 +911,22 @@ impl ProjectionPushDown {
                 let builder = ALogicalPlanBuilder::new(root, expr_arena, lp_arena);
                 Ok(self.finish_node(local_projection, builder))
             }
-            HStack { input, exprs, .. } => {
+            HStack {
+                input, mut exprs, ..
+            } => {
                 // Make sure that columns selected with_columns are available
                 // only if not empty. If empty we already select everything.
                 if !acc_projections.is_empty() {
+                    // the columns that are not projected above this node don't have to be
+                    // computed
+                    let input_schema = lp_arena.get(input).schema(lp_arena);
+                    exprs.retain(|e| {
+                        expr_arena
+                            .get(*e)
+                            .to_field(input_schema, Context::Default, expr_arena)
+                            .map(|field| projected_names.contains(field.name().as_str()))
+                            .unwrap_or(true)
+                    });
                     for expression in &exprs {
                         add_expr_to_accumulated(
                             *expression,
@@ -875,6 +951,9 @@ impl ProjectionPushDown {
                     lp_arena,
                     expr_arena,
                 )?;
+                if exprs.is_empty() {
+                    return Ok(lp_arena.take(input));
+                }
                 let lp = ALogicalPlanBuilder::new(input, expr_arena, lp_arena)
                     .with_columns(exprs)
                     .build();
@@ -920,8 +999,21 @@ impl ProjectionPushDown {
                     Ok(lp)
                 }
             }
-            // Slice and Cache have only inputs and exprs, so we can use same logic.
-            lp @ Slice { .. } | lp @ Cache { .. } | lp @ Union { .. } => {
+            // A cache may be shared with other branches of the plan, so we restart the projection
+            // pushdown below it and project on top of it.
+            Cache { input, id } => {
+                self.pushdown_and_assign(input, init_vec(), init_set(), 0, lp_arena, expr_arena)?;
+                let lp = Cache { input, id };
+                if acc_projections.is_empty() {
+                    Ok(lp)
+                } else {
+                    Ok(ALogicalPlanBuilder::from_lp(lp, expr_arena, lp_arena)
+                        .project(acc_projections)
+                        .build())
+                }
+            }
+            // Slice and Union have only inputs and exprs, so we can use same logic.
+            lp @ Slice { .. } | lp @ Union { .. } => {
                 let inputs = lp.get_inputs();
                 let exprs = lp.get_exprs();
 
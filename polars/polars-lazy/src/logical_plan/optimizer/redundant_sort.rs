@@ -0,0 +1,98 @@
+use crate::logical_plan::conversion::node_to_expr;
+use crate::prelude::stack_opt::OptimizationRule;
+use crate::prelude::*;
+use crate::utils::{aexpr_to_root_names, expr_output_name};
+
+/// Remove a sort of rows that are already sorted in the same way by a sort below it.
+///
+/// ```text
+/// SORT BY [col("a")]
+///     FILTER [(col("b")) > (1)]
+///         SORT BY [col("a")]
+///
+/// becomes
+///
+/// FILTER [(col("b")) > (1)]
+///     SORT BY [col("a")]
+/// ```
+///
+/// Only filters and projections that keep the sort columns unchanged may be in between, as
+/// these keep the order of the rows and the values they are sorted by.
+pub(crate) struct RemoveRedundantSort {}
+
+/// Check if a projection selects the columns unchanged.
+fn keeps_columns(exprs: &[Node], columns: &[Arc<str>], expr_arena: &Arena<AExpr>) -> bool {
+    columns.iter().all(|name| {
+        exprs
+            .iter()
+            .any(|node| matches!(expr_arena.get(*node), AExpr::Column(e) if e == name))
+    })
+}
+
+/// Check if none of the expressions of a `with_columns` overwrites one of the columns.
+fn overwrites_none(exprs: &[Node], columns: &[Arc<str>], expr_arena: &Arena<AExpr>) -> bool {
+    exprs.iter().all(|node| {
+        expr_output_name(&node_to_expr(*node, expr_arena))
+            .map(|output_name| !columns.contains(&output_name))
+            .unwrap_or(false)
+    })
+}
+
+/// Check if the output of `node` is sorted by `by_column` with `args`.
+fn is_sorted_by(
+    mut node: Node,
+    by_column: &[Node],
+    args: &SortArguments,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    use ALogicalPlan::*;
+    let columns = by_column
+        .iter()
+        .flat_map(|node| aexpr_to_root_names(*node, expr_arena))
+        .collect::<Vec<_>>();
+
+    loop {
+        node = match lp_arena.get(node) {
+            Sort {
+                by_column: sorted_by,
+                args: sorted_args,
+                ..
+            } => {
+                let same_expr = |(a, b): (&Node, &Node)| {
+                    node_to_expr(*a, expr_arena) == node_to_expr(*b, expr_arena)
+                };
+                return sorted_args == args
+                    && sorted_by.len() == by_column.len()
+                    && sorted_by.iter().zip(by_column).all(same_expr);
+            }
+            Selection { input, .. } => *input,
+            Projection { input, expr, .. } | LocalProjection { input, expr, .. }
+                if keeps_columns(expr, &columns, expr_arena) =>
+            {
+                *input
+            }
+            HStack { input, exprs, .. } if overwrites_none(exprs, &columns, expr_arena) => *input,
+            _ => return false,
+        }
+    }
+}
+
+impl OptimizationRule for RemoveRedundantSort {
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        let input = match lp_arena.get(node) {
+            ALogicalPlan::Sort {
+                input,
+                by_column,
+                args,
+            } if is_sorted_by(*input, by_column, args, lp_arena, expr_arena) => *input,
+            _ => return None,
+        };
+        Some(lp_arena.take(input))
+    }
+}
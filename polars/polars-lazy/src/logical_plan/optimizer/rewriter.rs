@@ -0,0 +1,153 @@
+use crate::logical_plan::conversion::{node_to_expr, node_to_lp, to_aexpr, to_alp};
+use crate::prelude::*;
+use polars_core::prelude::*;
+
+/// Rewrite a [`LogicalPlan`] with user defined rules.
+///
+/// By default a rewriter visits every node of the plan, from the scans to the root, and then every
+/// subexpression of the expressions of those nodes, from the root of an expression to its leaves.
+/// Override [`rewrite_node`](LogicalPlanRewriter::rewrite_node) or
+/// [`rewrite_expr`](LogicalPlanRewriter::rewrite_expr) to change the plan.
+///
+/// A rewriter can be registered with [`LazyFrame::with_optimization`] by implementing
+/// [`Optimize`](crate::prelude::Optimize) for it.
+pub trait LogicalPlanRewriter {
+    /// Rewrite the plan. The default rewrites the nodes with [`rewrite_nodes`] and then the
+    /// expressions with [`rewrite_exprs`].
+    fn rewrite_plan(&mut self, lp: LogicalPlan) -> Result<LogicalPlan> {
+        let lp = rewrite_nodes(self, lp)?;
+        rewrite_exprs(self, lp)
+    }
+
+    /// Rewrite a node of the plan. This is called after the inputs of the node are rewritten.
+    /// The default doesn't change the node.
+    fn rewrite_node(&mut self, lp: LogicalPlan) -> Result<LogicalPlan> {
+        Ok(lp)
+    }
+
+    /// Rewrite an expression. This is called for every subexpression, after the expression that
+    /// holds it, so the subexpressions of the rewritten expression are visited. The default
+    /// doesn't change the expression.
+    fn rewrite_expr(&mut self, expr: Expr) -> Result<Expr> {
+        Ok(expr)
+    }
+}
+
+/// Get mutable references to the inputs of a node.
+fn inputs_mut(lp: &mut LogicalPlan) -> Vec<&mut LogicalPlan> {
+    use LogicalPlan::*;
+    match lp {
+        Union { inputs, .. } => inputs.iter_mut().collect(),
+        Join {
+            input_left,
+            input_right,
+            ..
+        } => vec![&mut **input_left, &mut **input_right],
+        Selection { input, .. }
+        | Cache { input, .. }
+        | LocalProjection { input, .. }
+        | Projection { input, .. }
+        | Aggregate { input, .. }
+        | HStack { input, .. }
+        | Distinct { input, .. }
+        | Sort { input, .. }
+        | Explode { input, .. }
+        | Slice { input, .. }
+        | Melt { input, .. }
+        | Udf { input, .. }
+        | Error { input, .. } => vec![&mut **input],
+        #[cfg(feature = "csv-file")]
+        CsvScan { .. } => vec![],
+        #[cfg(feature = "parquet")]
+        ParquetScan { .. } => vec![],
+        #[cfg(feature = "ipc")]
+        IpcScan { .. } => vec![],
+        DataFrameScan { .. } => vec![],
+    }
+}
+
+/// Rewrite every node of `lp` with [`LogicalPlanRewriter::rewrite_node`], from the scans to the
+/// root.
+///
+/// The nodes are visited with an explicit stack, so that deep plans don't overflow the stack.
+pub fn rewrite_nodes<R: LogicalPlanRewriter + ?Sized>(
+    rewriter: &mut R,
+    lp: LogicalPlan,
+) -> Result<LogicalPlan> {
+    // a node is pushed once with its inputs taken out and once more when its inputs are
+    // rewritten, these are put back from `rewritten`
+    let mut stack = vec![(lp, false)];
+    let mut rewritten = vec![];
+    while let Some((mut lp, inputs_done)) = stack.pop() {
+        if inputs_done {
+            let inputs = inputs_mut(&mut lp);
+            let new_inputs = rewritten.split_off(rewritten.len() - inputs.len());
+            for (input, new_input) in inputs.into_iter().zip(new_inputs) {
+                *input = new_input;
+            }
+            rewritten.push(rewriter.rewrite_node(lp)?);
+        } else {
+            let inputs = inputs_mut(&mut lp)
+                .into_iter()
+                .map(std::mem::take)
+                .collect::<Vec<_>>();
+            stack.push((lp, true));
+            // the first input is on top of the stack, so it is rewritten first
+            stack.extend(inputs.into_iter().rev().map(|input| (input, false)));
+        }
+    }
+    Ok(rewritten.pop().unwrap())
+}
+
+/// Rewrite every subexpression of `expr` with [`LogicalPlanRewriter::rewrite_expr`].
+fn rewrite_expr_tree<R: LogicalPlanRewriter + ?Sized>(
+    rewriter: &mut R,
+    mut expr: Expr,
+) -> Result<Expr> {
+    let mut error = None;
+    expr.mutate()
+        .apply(|e| match rewriter.rewrite_expr(std::mem::take(e)) {
+            Ok(rewritten) => {
+                *e = rewritten;
+                true
+            }
+            Err(err) => {
+                error = Some(err);
+                false
+            }
+        });
+    match error {
+        Some(err) => Err(err),
+        None => Ok(expr),
+    }
+}
+
+/// Rewrite every expression of `lp` with [`LogicalPlanRewriter::rewrite_expr`].
+///
+/// The plan is converted to the arena once, the nodes are visited there from the root to the
+/// scans and the result is converted back once.
+pub fn rewrite_exprs<R: LogicalPlanRewriter + ?Sized>(
+    rewriter: &mut R,
+    lp: LogicalPlan,
+) -> Result<LogicalPlan> {
+    let mut expr_arena = Arena::with_capacity(16);
+    let mut lp_arena = Arena::with_capacity(8);
+    let root = to_alp(lp, &mut expr_arena, &mut lp_arena)?;
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        let alp = lp_arena.take(node);
+        let exprs = alp
+            .get_exprs()
+            .into_iter()
+            .map(|node| {
+                let expr = rewrite_expr_tree(rewriter, node_to_expr(node, &expr_arena))?;
+                Ok(to_aexpr(expr, &mut expr_arena))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let inputs = alp.get_inputs();
+        stack.extend_from_slice(&inputs);
+        lp_arena.replace(node, alp.with_exprs_and_input(exprs, inputs));
+    }
+    Ok(node_to_lp(root, &mut expr_arena, &mut lp_arena))
+}
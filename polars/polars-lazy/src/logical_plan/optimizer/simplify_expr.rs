@@ -1,7 +1,9 @@
 use polars_utils::arena::Arena;
 
+use crate::logical_plan::conversion::node_to_expr;
 use crate::logical_plan::optimizer::stack_opt::OptimizationRule;
 use crate::logical_plan::*;
+use crate::utils::aexpr_is_elementwise;
 
 macro_rules! eval_binary_same_type {
     ($lhs:expr, $operand: tt, $rhs:expr) => {{
@@ -158,7 +160,7 @@ impl OptimizationRule for SimplifyBooleanRule {
             {
                 Some(AExpr::Literal(LiteralValue::Boolean(false)))
             }
-            // false or x => x
+            // false OR x => x
             AExpr::BinaryExpr {
                 left,
                 op: Operator::Or,
@@ -170,56 +172,41 @@ impl OptimizationRule for SimplifyBooleanRule {
             {
                 Some(expr_arena.get(*right).clone())
             }
-            // x or false => x
-            AExpr::BinaryExpr {
-                op: Operator::Or,
-                right,
-                ..
-            } if matches!(
-                expr_arena.get(*right),
-                AExpr::Literal(LiteralValue::Boolean(false))
-            ) =>
-            {
-                Some(expr_arena.get(*right).clone())
-            }
-
-            // false OR x => x
+            // x OR false => x
             AExpr::BinaryExpr {
                 left,
                 op: Operator::Or,
                 right,
             } if matches!(
-                expr_arena.get(*left),
+                expr_arena.get(*right),
                 AExpr::Literal(LiteralValue::Boolean(false))
             ) =>
             {
-                Some(expr_arena.get(*right).clone())
+                Some(expr_arena.get(*left).clone())
             }
-
             // true OR x => true
             AExpr::BinaryExpr {
+                left,
                 op: Operator::Or,
-                right,
                 ..
             } if matches!(
-                expr_arena.get(*right),
+                expr_arena.get(*left),
                 AExpr::Literal(LiteralValue::Boolean(true))
             ) =>
             {
-                Some(AExpr::Literal(LiteralValue::Boolean(false)))
+                Some(AExpr::Literal(LiteralValue::Boolean(true)))
             }
-
             // x OR true => true
             AExpr::BinaryExpr {
                 op: Operator::Or,
-                left,
+                right,
                 ..
             } if matches!(
-                expr_arena.get(*left),
+                expr_arena.get(*right),
                 AExpr::Literal(LiteralValue::Boolean(true))
             ) =>
             {
-                Some(AExpr::Literal(LiteralValue::Boolean(false)))
+                Some(AExpr::Literal(LiteralValue::Boolean(true)))
             }
 
             AExpr::Not(x) => {
@@ -257,18 +244,139 @@ where
 
 pub struct SimplifyExprRule {}
 
+/// Check if the literal is a number equal to `value`.
+#[allow(clippy::float_cmp)]
+fn is_literal_number(lv: &LiteralValue, value: u8) -> bool {
+    match lv {
+        #[cfg(feature = "dtype-i8")]
+        LiteralValue::Int8(v) => *v == value as i8,
+        #[cfg(feature = "dtype-i16")]
+        LiteralValue::Int16(v) => *v == value as i16,
+        LiteralValue::Int32(v) => *v == value as i32,
+        LiteralValue::Int64(v) => *v == value as i64,
+        #[cfg(feature = "dtype-u8")]
+        LiteralValue::UInt8(v) => *v == value,
+        #[cfg(feature = "dtype-u16")]
+        LiteralValue::UInt16(v) => *v == value as u16,
+        LiteralValue::UInt32(v) => *v == value as u32,
+        LiteralValue::UInt64(v) => *v == value as u64,
+        LiteralValue::Float32(v) => *v == value as f32,
+        LiteralValue::Float64(v) => *v == value as f64,
+        _ => false,
+    }
+}
+
+/// Get the non literal operand of `x + 0`, `x - 0` or `x * 1`.
+///
+/// This is only done if the literal has the same data type as `x`, otherwise the operation
+/// would still cast `x` to the supertype. The literal must be the right operand, as the output
+/// name of a binary expression is the name of its left operand. The data type of `x` is only
+/// known in nodes that evaluate their expressions on the schema of their single input.
+fn identity_operand(
+    left: Node,
+    op: Operator,
+    right: Node,
+    expr_arena: &Arena<AExpr>,
+    lp_arena: &Arena<ALogicalPlan>,
+    lp_node: Node,
+) -> Option<Node> {
+    let literal = match (op, expr_arena.get(right)) {
+        (Operator::Plus | Operator::Minus, AExpr::Literal(lv)) if is_literal_number(lv, 0) => lv,
+        (Operator::Multiply, AExpr::Literal(lv)) if is_literal_number(lv, 1) => lv,
+        _ => return None,
+    };
+
+    use ALogicalPlan::*;
+    let input = match lp_arena.get(lp_node) {
+        Projection { input, .. } | HStack { input, .. } | Selection { input, .. } => *input,
+        _ => return None,
+    };
+    let input_schema = lp_arena.get(input).schema(lp_arena);
+    let dtype = expr_arena
+        .get(left)
+        .get_type(input_schema, Context::Default, expr_arena)
+        .ok()?;
+    if dtype == literal.get_datatype() {
+        Some(left)
+    } else {
+        None
+    }
+}
+
+/// Check if a literal is null. This is not known for a literal with multiple values.
+fn literal_is_null(lv: &LiteralValue) -> Option<bool> {
+    match lv {
+        LiteralValue::Null => Some(true),
+        LiteralValue::Series(_) | LiteralValue::Range { .. } => None,
+        _ => Some(false),
+    }
+}
+
+/// `x.is_null() AND x.is_not_null()` is always false and `x.is_null() OR x.is_not_null()` is
+/// always true.
+fn complementary_null_checks(
+    left: Node,
+    op: Operator,
+    right: Node,
+    expr_arena: &Arena<AExpr>,
+) -> Option<AExpr> {
+    let (a, b) = match (expr_arena.get(left), expr_arena.get(right)) {
+        (AExpr::IsNull(a), AExpr::IsNotNull(b)) | (AExpr::IsNotNull(a), AExpr::IsNull(b)) => {
+            (*a, *b)
+        }
+        _ => return None,
+    };
+    // both checks must be done on the same values
+    if !aexpr_is_elementwise(a, expr_arena)
+        || node_to_expr(a, expr_arena) != node_to_expr(b, expr_arena)
+    {
+        return None;
+    }
+    match op {
+        Operator::And => Some(AExpr::Literal(LiteralValue::Boolean(false))),
+        Operator::Or => Some(AExpr::Literal(LiteralValue::Boolean(true))),
+        _ => None,
+    }
+}
+
 impl OptimizationRule for SimplifyExprRule {
+    /// A selection with a predicate that is folded to a literal boolean either keeps all rows
+    /// and can be removed, or keeps no rows and can be replaced by an empty slice.
+    fn optimize_plan(
+        &mut self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        node: Node,
+    ) -> Option<ALogicalPlan> {
+        match lp_arena.get(node) {
+            ALogicalPlan::Selection { input, predicate } => {
+                let input = *input;
+                match expr_arena.get(*predicate) {
+                    AExpr::Literal(LiteralValue::Boolean(true)) => Some(lp_arena.take(input)),
+                    AExpr::Literal(LiteralValue::Boolean(false)) => Some(ALogicalPlan::Slice {
+                        input,
+                        offset: 0,
+                        len: 0,
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     #[allow(clippy::float_cmp)]
     fn optimize_expr(
         &self,
         expr_arena: &mut Arena<AExpr>,
         expr_node: Node,
-        _: &Arena<ALogicalPlan>,
-        _: Node,
+        lp_arena: &Arena<ALogicalPlan>,
+        lp_node: Node,
     ) -> Option<AExpr> {
         let expr = expr_arena.get(expr_node);
         match expr {
             // lit(left) + lit(right) => lit(left + right)
+            // x + 0 => x, x * 1 => x
             // and null propagation
             AExpr::BinaryExpr { left, op, right } => {
                 let left_aexpr = expr_arena.get(*left);
@@ -330,7 +438,7 @@ impl OptimizationRule for SimplifyExprRule {
                     Operator::Eq => eval_binary_bool_type!(left_aexpr, ==, right_aexpr),
                     Operator::NotEq => eval_binary_bool_type!(left_aexpr, !=, right_aexpr),
                     Operator::GtEq => eval_binary_bool_type!(left_aexpr, >=, right_aexpr),
-                    Operator::LtEq => eval_binary_bool_type!(left_aexpr, >=, right_aexpr),
+                    Operator::LtEq => eval_binary_bool_type!(left_aexpr, <=, right_aexpr),
                     Operator::And => eval_bitwise(left_aexpr, right_aexpr, |l, r| l & r),
                     Operator::Or => eval_bitwise(left_aexpr, right_aexpr, |l, r| l | r),
                     Operator::Xor => eval_bitwise(left_aexpr, right_aexpr, |l, r| l ^ r),
@@ -339,6 +447,18 @@ impl OptimizationRule for SimplifyExprRule {
                     return out;
                 }
 
+                // x + 0 => x, x * 1 => x
+                if let Some(operand) =
+                    identity_operand(*left, *op, *right, expr_arena, lp_arena, lp_node)
+                {
+                    return Some(expr_arena.get(operand).clone());
+                }
+
+                // x.is_null() AND x.is_not_null() => false
+                if let Some(out) = complementary_null_checks(*left, *op, *right, expr_arena) {
+                    return Some(out);
+                }
+
                 // Null propagation.
                 let left_is_null = matches!(left_aexpr, AExpr::Literal(LiteralValue::Null));
                 let right_is_null = matches!(right_aexpr, AExpr::Literal(LiteralValue::Null));
@@ -357,6 +477,18 @@ impl OptimizationRule for SimplifyExprRule {
                     _ => None,
                 }
             }
+            // lit(x).is_null() => false
+            AExpr::IsNull(input) => match expr_arena.get(*input) {
+                AExpr::Literal(lv) => literal_is_null(lv)
+                    .map(|is_null| AExpr::Literal(LiteralValue::Boolean(is_null))),
+                _ => None,
+            },
+            // lit(x).is_not_null() => true
+            AExpr::IsNotNull(input) => match expr_arena.get(*input) {
+                AExpr::Literal(lv) => literal_is_null(lv)
+                    .map(|is_null| AExpr::Literal(LiteralValue::Boolean(!is_null))),
+                _ => None,
+            },
             AExpr::Reverse(expr) => {
                 let input = expr_arena.get(*expr);
                 match input {
@@ -481,3 +613,132 @@ fn test_expr_to_aexp() {
         AExpr::Literal(LiteralValue::Int8(0))
     ))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use crate::tests::fruits_cars;
+    use crate::utils::test::{optimize_expr, optimize_lp};
+
+    fn simplify(expr: Expr) -> Expr {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SimplifyExprRule {})];
+        optimize_expr(expr, Schema::new(), &mut rules)
+    }
+
+    fn simplify_with_schema(expr: Expr, rules: &mut [Box<dyn OptimizationRule>]) -> Expr {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32),
+            Field::new("b", DataType::Boolean),
+        ]);
+        optimize_expr(expr, schema, rules)
+    }
+
+    #[test]
+    fn test_fold_integer_literals() {
+        assert_eq!(simplify(lit(2) + lit(3)), lit(5));
+        assert_eq!(simplify(lit(2i64) * lit(3i64) - lit(1i64)), lit(5i64));
+        assert_eq!(simplify(lit(7) % lit(4)), lit(3));
+    }
+
+    #[test]
+    fn test_fold_float_literals() {
+        assert_eq!(simplify(lit(1.5f64) + lit(2.0f64)), lit(3.5f64));
+        assert_eq!(simplify(lit(3.0f32) / lit(2.0f32)), lit(1.5f32));
+    }
+
+    #[test]
+    fn test_fold_boolean_literals() {
+        assert_eq!(simplify(lit(1).lt(lit(2))), lit(true));
+        assert_eq!(simplify(lit(1).lt_eq(lit(2))), lit(true));
+        assert_eq!(simplify(lit(2).lt_eq(lit(1))), lit(false));
+        assert_eq!(simplify(lit(2.0f64).gt_eq(lit(2.0f64))), lit(true));
+        assert_eq!(simplify(lit(1).eq(lit(2))), lit(false));
+        assert_eq!(simplify(lit(true).and(lit(false))), lit(false));
+        assert_eq!(simplify(lit(1).lt(lit(2)).or(lit(false))), lit(true));
+    }
+
+    #[test]
+    fn test_simplify_null_checks() {
+        assert_eq!(simplify(lit(5).is_null()), lit(false));
+        assert_eq!(simplify(lit(5).is_not_null()), lit(true));
+        assert_eq!(simplify(lit("a").is_null()), lit(false));
+        assert_eq!(simplify(lit(NULL).is_null()), lit(true));
+        assert_eq!(
+            simplify(col("a").is_not_null().and(col("a").is_null())),
+            lit(false)
+        );
+        assert_eq!(
+            simplify(col("a").is_null().or(col("a").is_not_null())),
+            lit(true)
+        );
+
+        // the checks are on other columns
+        let e = col("a").is_not_null().and(col("b").is_null());
+        assert_eq!(simplify(e.clone()), e);
+    }
+
+    #[test]
+    fn test_eliminate_constant_filters() -> Result<()> {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SimplifyExprRule {})];
+        let df = fruits_cars();
+
+        let lp = df.clone().lazy().filter(lit(1).lt(lit(2))).logical_plan;
+        let out = optimize_lp(lp, &mut rules);
+        assert!(matches!(out, LogicalPlan::DataFrameScan { .. }));
+
+        let lp = df.lazy().filter(lit(1).gt(lit(2))).logical_plan;
+        let out = optimize_lp(lp, &mut rules);
+        assert!(matches!(out, LogicalPlan::Slice { len: 0, .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_identity_operations() {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SimplifyExprRule {})];
+        let mut simplify = |expr| simplify_with_schema(expr, &mut rules);
+        assert_eq!(simplify(col("a") + lit(0)), col("a"));
+        assert_eq!(simplify(col("a") - lit(0)), col("a"));
+        assert_eq!(simplify(col("a") * lit(1)), col("a"));
+        assert_eq!(simplify(col("a") + (lit(1) - lit(1))), col("a"));
+
+        // no-ops
+        assert_eq!(simplify(col("a") + lit(1)), col("a") + lit(1));
+        assert_eq!(simplify(lit(0) - col("a")), lit(0) - col("a"));
+        // the output would be named after the column instead of the literal
+        assert_eq!(simplify(lit(0) + col("a")), lit(0) + col("a"));
+        assert_eq!(simplify(lit(1) * col("a")), lit(1) * col("a"));
+        assert_eq!(simplify(col("a") * lit(0)), col("a") * lit(0));
+        // the literal would still cast the column to a float
+        assert_eq!(simplify(col("a") + lit(0.0)), col("a") + lit(0.0));
+    }
+
+    #[test]
+    fn test_keep_identity_operations_in_join_keys() {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SimplifyExprRule {})];
+        let df = fruits_cars();
+        // the right keys are not evaluated on the schema of the left input
+        let lp = df
+            .clone()
+            .lazy()
+            .join(df.lazy(), [col("A")], [col("B") + lit(0)], JoinType::Inner)
+            .logical_plan;
+        match optimize_lp(lp, &mut rules) {
+            LogicalPlan::Join { right_on, .. } => assert_eq!(right_on, [col("B") + lit(0)]),
+            lp => panic!("expected a join, got {:?}", lp),
+        }
+    }
+
+    #[test]
+    fn test_remove_and_true() {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(SimplifyBooleanRule {})];
+        assert_eq!(
+            simplify_with_schema(col("b").and(lit(true)), &mut rules),
+            col("b")
+        );
+        assert_eq!(
+            simplify_with_schema(lit(true).and(col("b")), &mut rules),
+            col("b")
+        );
+    }
+}
@@ -83,15 +83,16 @@ impl OptimizationRule for TypeCoercionRule {
                     let falsy = expr_arena.get(falsy_node);
                     let type_true = truthy
                         .get_type(input_schema, Context::Default, expr_arena)
-                        .expect("could not dtype");
+                        .ok()?;
                     let type_false = falsy
                         .get_type(input_schema, Context::Default, expr_arena)
-                        .expect("could not dtype");
+                        .ok()?;
 
                     if type_true == type_false {
                         None
                     } else {
-                        let st = get_supertype(&type_true, &type_false).expect("supertype");
+                        // no supertype; leave the error to the physical expression
+                        let st = get_supertype(&type_true, &type_false).ok()?;
                         let st = use_supertype(st, truthy, falsy, &type_true, &type_false);
 
                         // only cast if the type is not already the super type.
@@ -204,8 +205,8 @@ impl OptimizationRule for TypeCoercionRule {
                     if type_left == type_right || compare_cat_to_string || datetime_arithmetic {
                         None
                     } else {
-                        let st = get_supertype(&type_left, &type_right)
-                            .expect("could not find supertype of binary expr");
+                        // no supertype; leave the error to the physical expression
+                        let st = get_supertype(&type_left, &type_right).ok()?;
 
                         let mut st = use_supertype(st, left, right, &type_left, &type_right);
 
@@ -267,6 +268,7 @@ mod test {
     use crate::logical_plan::optimizer::stack_opt::OptimizationRule;
     use crate::prelude::*;
     use crate::utils::test::optimize_expr;
+    use polars_core::df;
     use polars_core::prelude::*;
 
     #[test]
@@ -284,4 +286,50 @@ mod test {
         let expected = col("fruits").cast(DataType::Utf8) + lit("somestr");
         assert_eq!(out, expected);
     }
+
+    #[test]
+    fn test_int_float_coercion() {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(TypeCoercionRule {})];
+        let schema = Schema::from(vec![Field::new("a", DataType::Int64)]);
+
+        // the integer column is cast, the float literal is not truncated
+        let expr = col("a").gt(lit(1.5f64));
+        let out = optimize_expr(expr, schema.clone(), &mut rules);
+        assert_eq!(out, col("a").cast(DataType::Float64).gt(lit(1.5f64)));
+
+        // an integer literal is cast to the type of the column
+        let expr = col("a") + lit(1i32);
+        let out = optimize_expr(expr, schema, &mut rules);
+        assert_eq!(out, col("a") + lit(1i32).cast(DataType::Int64));
+    }
+
+    #[test]
+    fn test_int32_int64_coercion() {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(TypeCoercionRule {})];
+        let schema = Schema::from(vec![
+            Field::new("a", DataType::Int32),
+            Field::new("b", DataType::Int64),
+        ]);
+
+        let expr = col("a") + col("b");
+        let out = optimize_expr(expr, schema, &mut rules);
+        assert_eq!(out, col("a").cast(DataType::Int64) + col("b"));
+    }
+
+    #[test]
+    fn test_utf8_numeric_comparison_not_coerced() -> Result<()> {
+        let mut rules: Vec<Box<dyn OptimizationRule>> = vec![Box::new(TypeCoercionRule {})];
+        let schema = Schema::from(vec![Field::new("s", DataType::Utf8)]);
+
+        let expr = col("s").gt(lit(1));
+        let out = optimize_expr(expr.clone(), schema, &mut rules);
+        assert_eq!(out, expr);
+
+        let df = df![
+            "s" => ["a", "b"]
+        ]?;
+        let out = df.lazy().filter(col("s").gt(lit(1))).collect();
+        assert!(out.is_err());
+        Ok(())
+    }
 }
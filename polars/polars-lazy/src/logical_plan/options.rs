@@ -142,7 +142,7 @@ pub struct LogicalPlanUdfOptions {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SortArguments {
     pub(crate) reverse: Vec<bool>,
-    // Can only be true in case of a single column.
-    pub(crate) nulls_last: bool,
+    // Whether the nulls of each sort key are placed last.
+    pub(crate) nulls_last: Vec<bool>,
     pub(crate) slice: Option<(i64, usize)>,
 }
@@ -330,6 +330,29 @@ fn function_wildcard_expansion(mut expr: Expr, schema: &Schema, exclude: &[Arc<s
     expr
 }
 
+/// Check that the columns excluded by name exist in the schema. Regex patterns may match no column.
+pub(crate) fn check_excluded(exprs: &[Expr], schema: &Schema) -> Result<()> {
+    for expr in exprs {
+        for e in expr {
+            if let Expr::Exclude(_, to_exclude) = e {
+                for to_exclude_single in to_exclude {
+                    if let Excluded::Name(name) = to_exclude_single {
+                        let is_regex = name.starts_with('^') && name.ends_with('$');
+                        if !is_regex && schema.get(name).is_none() {
+                            return Err(PolarsError::NotFound(format!(
+                                "cannot exclude column {}; available columns: {:?}",
+                                name,
+                                schema.iter_names().collect::<Vec<_>>()
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// In case of single col(*) -> do nothing, no selection is the same as select all
 /// In other cases replace the wildcard with an expression with all columns
 pub(crate) fn rewrite_projections(exprs: Vec<Expr>, schema: &Schema, keys: &[Expr]) -> Vec<Expr> {
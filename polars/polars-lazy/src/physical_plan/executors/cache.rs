@@ -4,21 +4,29 @@ use crate::prelude::*;
 use polars_core::prelude::*;
 
 pub struct CacheExec {
-    pub key: String,
+    pub id: usize,
     pub input: Box<dyn Executor>,
 }
 
 impl Executor for CacheExec {
     fn execute(&mut self, state: &ExecutionState) -> Result<DataFrame> {
-        if let Some(df) = state.cache_hit(&self.key) {
-            return Ok(df);
+        let slot = state.cache_slot(self.id);
+        if let Some(df) = slot.lock().as_ref() {
+            return Ok(df.clone());
         }
 
         // cache miss
+        // the slot is not locked while the input is executed, as a rayon thread that waits on the
+        // lock could pick up the job of a cache with the same id and deadlock. Caches with the
+        // same id that miss at the same time both compute the input and the first result is kept.
         let df = self.input.execute(state)?;
-        state.store_cache(std::mem::take(&mut self.key), df.clone());
+        let mut cached = slot.lock();
+        if let Some(df) = cached.as_ref() {
+            return Ok(df.clone());
+        }
+        *cached = Some(df.clone());
         if std::env::var(POLARS_VERBOSE).is_ok() {
-            println!("cache set {:?}", self.key);
+            println!("cache set {:?}", self.id);
         }
         Ok(df)
     }
@@ -28,11 +28,44 @@ impl Executor for SortExec {
                 Ok(s)
             })
             .collect::<Result<Vec<_>>>()?;
-        df.sort_impl(
-            by_columns,
-            std::mem::take(&mut self.args.reverse),
-            self.args.nulls_last,
-            self.args.slice,
-        )
+        let reverse = std::mem::take(&mut self.args.reverse);
+        let nulls_last = &self.args.nulls_last;
+
+        // a single ascending key can place its nulls anywhere, a single descending key only last
+        if by_columns.len() == 1 && (!reverse[0] || nulls_last[0]) {
+            return df.sort_impl(by_columns, reverse, nulls_last[0], self.args.slice);
+        }
+        let (by_columns, reverse) = with_null_order_keys(by_columns, &reverse, nulls_last);
+        df.sort_impl(by_columns, reverse, false, self.args.slice)
+    }
+}
+
+/// Sorting by multiple keys places the nulls of a key first in an ascending order and last in a
+/// descending order. If the nulls of a key should be placed otherwise, a key that orders the nulls
+/// of that key is inserted before it.
+fn with_null_order_keys(
+    by_columns: Vec<Series>,
+    reverse: &[bool],
+    nulls_last: &[bool],
+) -> (Vec<Series>, Vec<bool>) {
+    // the builder checks that there is a flag per key
+    let mut keys = Vec::with_capacity(by_columns.len());
+    let mut keys_reverse = Vec::with_capacity(by_columns.len());
+    for (i, ((s, &reverse), &nulls_last)) in by_columns
+        .into_iter()
+        .zip(reverse)
+        .zip(nulls_last)
+        .enumerate()
+    {
+        if nulls_last != reverse && s.null_count() > 0 {
+            let mut is_null = s.is_null().into_series();
+            is_null.rename(&format!("_POLARS_SORT_NULLS_{}", i));
+            keys.push(is_null);
+            // `false` sorts before `true`
+            keys_reverse.push(!nulls_last);
+        }
+        keys.push(s);
+        keys_reverse.push(reverse);
     }
+    (keys, keys_reverse)
 }
@@ -53,6 +53,12 @@ impl Executor for UnionExec {
             })?
         };
 
-        concat_df(&dfs)
+        let df = concat_df(&dfs)?;
+        // a negative offset counts from the end, so it is applied to the concatenated result
+        if self.options.slice && self.options.slice_offset < 0 {
+            Ok(df.slice(self.options.slice_offset, self.options.slice_len as usize))
+        } else {
+            Ok(df)
+        }
     }
 }
@@ -46,7 +46,11 @@ impl CastExpr {
         }
 
         if self.strict {
-            input.strict_cast(&self.data_type)
+            input.strict_cast(&self.data_type).map_err(|err| {
+                PolarsError::ComputeError(
+                    format!("cannot cast column {:?}: {}", input.name(), err).into(),
+                )
+            })
         } else {
             input.cast(&self.data_type)
         }
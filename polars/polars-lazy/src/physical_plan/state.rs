@@ -6,11 +6,14 @@ use std::ops::Deref;
 
 pub type JoinTuplesCache = Arc<Mutex<PlHashMap<String, JoinOptIds>>>;
 pub type GroupsProxyCache = Arc<Mutex<PlHashMap<String, GroupsProxy>>>;
+pub type CacheSlot = Arc<Mutex<Option<DataFrame>>>;
 
 /// State/ cache that is maintained during the Execution of the physical plan.
 #[derive(Clone)]
 pub struct ExecutionState {
     df_cache: Arc<Mutex<PlHashMap<String, DataFrame>>>,
+    /// Results of the `Cache` nodes by id
+    cache_slots: Arc<Mutex<PlHashMap<usize, CacheSlot>>>,
     pub schema_cache: Arc<RwLock<Option<SchemaRef>>>,
     /// Used by Window Expression to prevent redundant grouping
     pub(crate) group_tuples: GroupsProxyCache,
@@ -24,6 +27,7 @@ impl ExecutionState {
     pub fn new() -> Self {
         Self {
             df_cache: Arc::new(Mutex::new(PlHashMap::default())),
+            cache_slots: Arc::new(Mutex::new(PlHashMap::default())),
             schema_cache: Arc::new(RwLock::new(None)),
             group_tuples: Arc::new(Mutex::new(PlHashMap::default())),
             join_tuples: Arc::new(Mutex::new(PlHashMap::default())),
@@ -68,6 +72,12 @@ impl ExecutionState {
         guard.insert(key, df);
     }
 
+    /// Get the slot that holds the result of the `Cache` nodes with `id`.
+    pub(crate) fn cache_slot(&self, id: usize) -> CacheSlot {
+        let mut guard = self.cache_slots.lock();
+        guard.entry(id).or_default().clone()
+    }
+
     /// Clear the cache used by the Window expressions
     pub(crate) fn clear_expr_cache(&self) {
         {
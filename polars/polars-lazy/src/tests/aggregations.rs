@@ -549,3 +549,71 @@ fn test_take_in_groups() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_agg_expr_dtypes() -> Result<()> {
+    let df = fruits_cars();
+
+    let out = df
+        .lazy()
+        .groupby_stable([col("cars")])
+        .agg([
+            col("A").sum().alias("sum"),
+            col("A").min().alias("min"),
+            col("A").max().alias("max"),
+            col("A").mean().alias("mean"),
+            col("A").count().alias("count"),
+            col("A").first().alias("first"),
+            col("A").last().alias("last"),
+        ])
+        .collect()?;
+
+    assert_eq!(out.column("count")?.dtype(), &DataType::UInt32);
+    assert_eq!(out.column("mean")?.dtype(), &DataType::Float64);
+    assert_eq!(Vec::from(out.column("count")?.u32()?), &[Some(4), Some(1)]);
+    assert_eq!(
+        Vec::from(out.column("mean")?.f64()?),
+        &[Some(3.25), Some(2.0)]
+    );
+    for (name, expected) in [
+        ("sum", [13, 2]),
+        ("min", [1, 2]),
+        ("max", [5, 2]),
+        ("first", [1, 2]),
+        ("last", [5, 2]),
+    ] {
+        let s = out.column(name)?.cast(&DataType::Int64)?;
+        assert_eq!(Vec::from(s.i64()?), &[Some(expected[0]), Some(expected[1])]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_groupby_multiple_keys() -> Result<()> {
+    let df = fruits_cars();
+
+    let q = df
+        .lazy()
+        .groupby_stable([col("fruits"), col("cars")])
+        .agg([col("B").sum()])
+        .select([col("cars"), col("fruits"), col("B")]);
+
+    let out = q.clone().collect()?;
+    let expected = q.with_projection_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(out.shape(), (3, 3));
+    assert_eq!(out.get_column_names(), &["cars", "fruits", "B"]);
+
+    let out = fruits_cars()
+        .lazy()
+        .groupby_stable([col("fruits"), col("cars")])
+        .agg([col("B").sum()])
+        .collect()?;
+    assert_eq!(out.get_column_names(), &["fruits", "cars", "B"]);
+    let b = out.column("B")?.cast(&DataType::Int64)?;
+    // (banana, beetle), (banana, audi), (apple, beetle)
+    assert_eq!(Vec::from(b.i64()?), &[Some(6), Some(4), Some(5)]);
+
+    Ok(())
+}
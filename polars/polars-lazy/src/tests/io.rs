@@ -1,5 +1,6 @@
 use super::*;
 use polars_io::RowCount;
+use std::path::PathBuf;
 
 #[test]
 fn test_parquet_exec() -> Result<()> {
@@ -239,6 +240,60 @@ fn test_csv_globbing() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_csv_scan_paths() -> Result<()> {
+    let paths = vec![
+        PathBuf::from("../../examples/datasets/foods1.csv"),
+        PathBuf::from("../../examples/datasets/foods2.csv"),
+    ];
+    let df = LazyCsvReader::new_paths(paths.clone())
+        .finish()?
+        .collect()?;
+    assert_eq!(df.shape(), (54, 4));
+
+    // the predicate and projection are pushed down to both files
+    let q = LazyCsvReader::new_paths(paths.clone())
+        .finish()?
+        .filter(col("sugars_g").lt(lit(1i32)))
+        .select([col("category")]);
+    assert_eq!(predicates_at_scans(q.clone()), 2);
+    let expected = df
+        .lazy()
+        .filter(col("sugars_g").lt(lit(1i32)))
+        .select([col("category")])
+        .collect()?;
+    assert!(q.collect()?.frame_equal(&expected));
+
+    // skipping rows without a row limit reads the rest of the files
+    let out = LazyCsvReader::new_paths(paths.clone())
+        .with_skip_rows(4)
+        .finish()?
+        .collect()?;
+    assert_eq!(out.height(), 50);
+
+    // the schema is inferred from the first file
+    let out = LazyCsvReader::new_paths(paths.clone())
+        .with_schema_modify(Ok)?
+        .finish()?
+        .collect()?;
+    assert_eq!(out.shape(), (54, 4));
+
+    let err = LazyCsvReader::new("../../examples/datasets/*.no_such_csv".into())
+        .finish()
+        .unwrap_err();
+    assert!(err.to_string().contains("no matching files found"));
+
+    let other = std::env::temp_dir().join("polars_test_csv_scan_paths.csv");
+    std::fs::write(&other, "a,b\n1,2\n")?;
+    let mut paths = paths;
+    paths.push(other.clone());
+    let err = LazyCsvReader::new_paths(paths).finish().unwrap_err();
+    assert!(err.to_string().contains("does not match the schema"));
+    std::fs::remove_file(&other)?;
+
+    Ok(())
+}
+
 #[test]
 pub fn test_simple_slice() -> Result<()> {
     let _guard = SINGLE_LOCK.lock().unwrap();
@@ -405,3 +460,58 @@ fn scan_predicate_on_set_null_values() -> Result<()> {
     assert_eq!(df.shape(), (12, 2));
     Ok(())
 }
+
+#[test]
+fn test_csv_scan_dtype_overwrite() -> Result<()> {
+    let mut overwrite = Schema::new();
+    overwrite.with_column("calories".to_string(), DataType::Utf8);
+
+    let q = LazyCsvReader::new(FOODS_CSV.into())
+        .with_dtype_overwrite(Some(&overwrite))
+        .finish()?;
+    // the other columns are inferred
+    let schema = q.schema();
+    assert_eq!(schema.get("calories"), Some(&DataType::Utf8));
+    assert_eq!(schema.get("fats_g"), Some(&DataType::Float64));
+
+    let out = q
+        .select([col("category"), col("calories")])
+        .filter(col("calories").eq(lit("45")))
+        .collect()?;
+    assert_eq!(out.column("calories")?.dtype(), &DataType::Utf8);
+    assert!(out.height() > 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_parquet_scan_pushdown() -> Result<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    let q = scan_foods_parquet(false)
+        .select([col("category"), col("sugars_g")])
+        .filter(col("category").eq(lit("seafood")));
+
+    // both the projection and the predicate are done by the reader
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let (with_columns, has_predicate) = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::ParquetScan {
+                options, predicate, ..
+            } => Some((options.with_columns.clone(), predicate.is_some())),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        with_columns,
+        Some(vec!["category".to_string(), "sugars_g".to_string()])
+    );
+    assert!(has_predicate);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["category", "sugars_g"]);
+    assert_eq!(out.shape(), (8, 2));
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use super::*;
 
-fn get_arenas() -> (Arena<AExpr>, Arena<ALogicalPlan>) {
+pub(crate) fn get_arenas() -> (Arena<AExpr>, Arena<ALogicalPlan>) {
     let expr_arena = Arena::with_capacity(16);
     let lp_arena = Arena::with_capacity(8);
     (expr_arena, lp_arena)
@@ -39,28 +39,39 @@ pub(crate) fn row_count_at_scan(q: LazyFrame) -> bool {
     })
 }
 
+fn has_predicate_at_scan(lp: &ALogicalPlan) -> bool {
+    use ALogicalPlan::*;
+    matches!(
+        lp,
+        DataFrameScan {
+            selection: Some(_),
+            ..
+        } | CsvScan {
+            predicate: Some(_),
+            ..
+        } | ParquetScan {
+            predicate: Some(_),
+            ..
+        } | IpcScan {
+            predicate: Some(_),
+            ..
+        }
+    )
+}
+
 pub(crate) fn predicate_at_scan(q: LazyFrame) -> bool {
+    predicates_at_scans(q) > 0
+}
+
+/// Count the scans that have a predicate pushed down to them.
+pub(crate) fn predicates_at_scans(q: LazyFrame) -> usize {
     let (mut expr_arena, mut lp_arena) = get_arenas();
     let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
 
-    (&lp_arena).iter(lp).any(|(_, lp)| {
-        use ALogicalPlan::*;
-        match lp {
-            DataFrameScan {
-                selection: Some(_), ..
-            }
-            | CsvScan {
-                predicate: Some(_), ..
-            }
-            | ParquetScan {
-                predicate: Some(_), ..
-            }
-            | IpcScan {
-                predicate: Some(_), ..
-            } => true,
-            _ => false,
-        }
-    })
+    (&lp_arena)
+        .iter(lp)
+        .filter(|(_, lp)| has_predicate_at_scan(lp))
+        .count()
 }
 
 fn slice_at_scan(q: LazyFrame) -> bool {
@@ -161,6 +172,67 @@ pub fn test_simple_slice() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_slice_pushdown_csv() -> Result<()> {
+    let q = scan_foods_csv()
+        .select([col("category"), col("calories")])
+        .limit(5);
+    assert!(slice_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.shape(), (5, 2));
+
+    // the offset is skipped by the reader
+    let q = scan_foods_csv().slice(3, 4);
+    assert!(slice_at_scan(q.clone()));
+    let expected = scan_foods_csv().collect()?.slice(3, 4);
+    assert!(q.collect()?.frame_equal(&expected));
+
+    // a slice is not pushed past a filter
+    let q = scan_foods_csv()
+        .filter(col("category").eq(lit("fruit")))
+        .limit(2);
+    assert!(!slice_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.height(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_limit_stays_above_reverse() -> Result<()> {
+    let df = fruits_cars();
+    let q = df
+        .clone()
+        .lazy()
+        .reverse()
+        .filter(col("A").gt(lit(1)))
+        .select([col("A")])
+        .limit(2);
+
+    // a limit of the reversed rows takes the last rows of the input, so it is not pushed below
+    // the reverse, the filter is
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let slice_input = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::Slice { input, .. } => Some(*input),
+            _ => None,
+        })
+        .unwrap();
+    assert!((&lp_arena)
+        .iter(slice_input)
+        .any(|(_, lp)| matches!(lp, ALogicalPlan::LocalProjection { .. })));
+    assert!(predicate_at_scan(q.clone()));
+
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(5), Some(4)]);
+    let out = df.lazy().reverse().limit(2).collect()?;
+    assert_eq!(Vec::from(out.column("B")?.i32()?), &[Some(1), Some(2)]);
+
+    Ok(())
+}
+
 #[test]
 pub fn test_slice_pushdown_join() -> Result<()> {
     let _guard = SINGLE_LOCK.lock().unwrap();
@@ -302,6 +374,49 @@ fn test_lazy_filter_and_rename() {
     assert_eq!(lf.collect().unwrap().get_column_names(), &["x", "b", "c"]);
 }
 
+#[test]
+fn test_filter_on_renamed_column() -> Result<()> {
+    let df = fruits_cars();
+
+    // the predicate reaches the scan with the original name
+    let q = df
+        .clone()
+        .lazy()
+        .rename(["A"], ["x"])
+        .filter(col("x").gt(lit(3)));
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let selection = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::DataFrameScan { selection, .. } => *selection,
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        crate::utils::aexpr_to_root_names(selection, &expr_arena),
+        &[Arc::from("A")]
+    );
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("x")?.i32()?), &[Some(4), Some(5)]);
+
+    // swapped names must not be pushed down with the same name
+    let q = df
+        .lazy()
+        .rename(["A", "B"], ["B", "A"])
+        .filter(col("B").gt(lit(3)));
+    assert_eq!(
+        q.schema().iter_names().collect::<Vec<_>>(),
+        &["B", "fruits", "A", "cars"]
+    );
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["B", "fruits", "A", "cars"]);
+    assert_eq!(Vec::from(out.column("B")?.i32()?), &[Some(4), Some(5)]);
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(2), Some(1)]);
+
+    Ok(())
+}
+
 #[test]
 fn test_with_row_count_opts() -> Result<()> {
     let df = df![
@@ -377,3 +492,826 @@ fn test_with_row_count_opts() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_combined_predicate_at_scan_is_deterministic() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .filter(col("A").gt(lit(1)))
+        .filter(col("B").gt(lit(1)))
+        .filter(col("fruits").eq(lit("banana")))
+        .filter(col("cars").eq(lit("beetle")));
+
+    // all predicates are combined into a single predicate at the scan
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(!(&lp_arena)
+        .iter(lp)
+        .any(|(_, lp)| matches!(lp, ALogicalPlan::Selection { .. })));
+
+    // and they are always combined in the same order
+    let plan = q.describe_optimized_plan()?;
+    for _ in 0..10 {
+        assert_eq!(q.describe_optimized_plan()?, plan);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_optimize_optimized_plan() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .filter(col("A").gt(lit(1)))
+        .select([col("fruits"), col("B")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp_top = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let optimized = LazyFrame::from(node_to_lp(lp_top, &mut expr_arena, &mut lp_arena));
+
+    // the pushdowns are repeated until the plan doesn't change, running the optimizer on the
+    // optimized plan should not change its outcome
+    assert!(predicate_at_scan(optimized.clone()));
+    assert!(optimized.collect()?.frame_equal(&q.collect()?));
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_through_merged_projections() -> Result<()> {
+    // the predicate refers to a name that only exists after both projections; it is renamed
+    // while it is pushed down, so it reaches the scan without another optimization round
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A").alias("x"), col("B")])
+        .select([col("x").alias("y"), col("B")])
+        .filter(col("y").gt(lit(2)));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("y")?.i32()?),
+        &[Some(3), Some(4), Some(5)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_slice_pushdown_projection_in_memory() -> Result<()> {
+    let df = fruits_cars();
+    let q = df.lazy().select([col("A")]).limit(3);
+
+    // the slice is done directly on the scan, before the projection
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!((&lp_arena).iter(lp).any(|(_, lp)| match lp {
+        ALogicalPlan::Slice { input, .. } => {
+            matches!(lp_arena.get(*input), ALogicalPlan::DataFrameScan { .. })
+        }
+        _ => false,
+    }));
+
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("A")?.i32()?),
+        &[Some(1), Some(2), Some(3)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_slice_not_pushed_past_filter() -> Result<()> {
+    let df = fruits_cars();
+    let q = df.clone().lazy().filter(col("A").gt(lit(1))).slice(1, 2);
+
+    // the filter is done in the scan, the slice on its output
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    match lp_arena.get(lp) {
+        ALogicalPlan::Slice {
+            input, offset: 1, ..
+        } => assert!(matches!(
+            lp_arena.get(*input),
+            ALogicalPlan::DataFrameScan {
+                selection: Some(_),
+                ..
+            }
+        )),
+        _ => panic!("expected a slice on top of the plan"),
+    }
+
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(3), Some(4)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_slice_negative_offset() -> Result<()> {
+    let df = fruits_cars();
+    let out = df.clone().lazy().slice(-3, 2).collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(3), Some(4)]);
+
+    // the slice is pushed into the union
+    let q = concat([df.clone().lazy(), df.lazy()], false)?.slice(-3, 2);
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(3), Some(4)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_pushed_into_union() -> Result<()> {
+    let df = fruits_cars();
+    let q = concat([df.clone().lazy(), df.clone().lazy()], false)?
+        .filter(col("A").gt(lit(3)))
+        .select([col("A"), col("fruits")]);
+    assert_eq!(predicates_at_scans(q.clone()), 2);
+    assert_eq!(count_selections(q.clone()), 0);
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("A")?.i32()?),
+        &[Some(4), Some(5), Some(4), Some(5)]
+    );
+
+    // the inputs must have the same schema
+    let other = df.clone().lazy().select([col("A")]);
+    let err = concat([df.lazy(), other], false).unwrap_err();
+    assert!(err.to_string().contains("different schemas"));
+
+    Ok(())
+}
+
+#[test]
+fn test_selection_shown_on_dataframe_scan() -> Result<()> {
+    let df = fruits_cars();
+
+    let plan = df.clone().lazy().describe_optimized_plan()?;
+    assert!(plan.contains("selection: \"None\""));
+
+    let q = df.lazy().filter(col("A").gt(lit(1)));
+    let plan = q.describe_optimized_plan()?;
+    assert!(!plan.contains("FILTER"));
+    assert!(!plan.contains("selection: \"None\""));
+    assert_eq!(q.collect()?.height(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_chained_projections() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), (col("B") * lit(2)).alias("B2")])
+        .select([col("B2"), col("A")])
+        .select([col("B2").alias("C"), col("A")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let n_projections = (&lp_arena)
+        .iter(lp)
+        .filter(|(_, lp)| {
+            use ALogicalPlan::*;
+            matches!(lp, Projection { .. } | LocalProjection { .. } | Udf { .. })
+        })
+        .count();
+    assert_eq!(n_projections, 1);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["C", "A"]);
+    assert_eq!(
+        Vec::from(out.column("C")?.i32()?),
+        &[Some(10), Some(8), Some(6), Some(4), Some(2)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_compose_stacked_projections() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), (col("B") * lit(2)).alias("B2"), col("fruits")])
+        .select([(col("B2") + col("A")).alias("C"), col("A")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    match lp_arena.get(lp) {
+        ALogicalPlan::Projection { input, .. } => {
+            assert!(matches!(
+                lp_arena.get(*input),
+                ALogicalPlan::DataFrameScan { .. }
+            ))
+        }
+        _ => panic!("expected a projection over the scan"),
+    }
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["C", "A"]);
+    assert_eq!(
+        Vec::from(out.column("C")?.i32()?),
+        &[Some(11), Some(10), Some(9), Some(8), Some(7)]
+    );
+
+    // the product would be computed twice
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), (col("B") * lit(2)).alias("B2")])
+        .select([(col("B2") * col("B2")).alias("C")]);
+    assert_eq!(count_projections(q.clone()), 2);
+    assert_eq!(
+        Vec::from(q.collect()?.column("C")?.i32()?),
+        &[Some(100), Some(64), Some(36), Some(16), Some(4)]
+    );
+
+    Ok(())
+}
+
+fn count_selections(q: LazyFrame) -> usize {
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+    (&lp_arena)
+        .iter(lp)
+        .filter(|(_, lp)| matches!(lp, ALogicalPlan::Selection { .. }))
+        .count()
+}
+
+#[test]
+fn test_combine_chained_filters() -> Result<()> {
+    let df = df![
+        "a" => [Some(1), None, Some(3), Some(4), Some(5), Some(6)],
+        "b" => [Some(1), Some(2), None, Some(4), Some(5), Some(6)],
+    ]?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .filter(col("a").gt(lit(0)))
+        .filter(col("b").gt(lit(0)))
+        .filter(col("a").lt(lit(6)))
+        .filter(col("b").neq(lit(5)))
+        .filter(col("a").gt_eq(col("b")))
+        .with_predicate_pushdown(false);
+
+    assert_eq!(count_selections(q.clone()), 1);
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("a")?.i32()?), &[Some(1), Some(4)]);
+
+    // the second predicate depends on the rows that remain after the first filter
+    let q = df
+        .lazy()
+        .filter(col("a").gt(lit(1)))
+        .filter(col("a").gt(col("a").mean()))
+        .with_predicate_pushdown(false);
+
+    assert_eq!(count_selections(q.clone()), 2);
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("a")?.i32()?), &[Some(5), Some(6)]);
+
+    Ok(())
+}
+
+fn count_projections(q: LazyFrame) -> usize {
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+    (&lp_arena)
+        .iter(lp)
+        .filter(|(_, lp)| {
+            use ALogicalPlan::*;
+            matches!(lp, Projection { .. } | LocalProjection { .. } | Udf { .. })
+        })
+        .count()
+}
+
+#[test]
+fn test_remove_identity_projection() -> Result<()> {
+    let df = fruits_cars();
+
+    // projection pushdown is turned off, so that the scan keeps the column order of the frame
+    let q = df
+        .clone()
+        .lazy()
+        .select([col("A"), col("fruits"), col("B"), col("cars")])
+        .with_projection_pushdown(false);
+    assert_eq!(count_projections(q.clone()), 0);
+    assert!(q.collect()?.frame_equal(&df));
+
+    // a reordering is kept
+    let q = df
+        .clone()
+        .lazy()
+        .select([col("fruits"), col("A"), col("B"), col("cars")])
+        .with_projection_pushdown(false);
+    assert_eq!(count_projections(q.clone()), 1);
+    assert_eq!(
+        q.collect()?.get_column_names(),
+        &["fruits", "A", "B", "cars"]
+    );
+
+    // a rename is kept
+    let q = df
+        .clone()
+        .lazy()
+        .select([col("A").alias("a"), col("fruits"), col("B"), col("cars")])
+        .with_projection_pushdown(false);
+    assert_eq!(count_projections(q.clone()), 1);
+    assert_eq!(
+        q.collect()?.get_column_names(),
+        &["a", "fruits", "B", "cars"]
+    );
+
+    // a cast is kept
+    let q = df
+        .clone()
+        .lazy()
+        .select([
+            col("A").cast(DataType::Float64),
+            col("fruits"),
+            col("B"),
+            col("cars"),
+        ])
+        .with_projection_pushdown(false);
+    assert_eq!(count_projections(q.clone()), 1);
+    assert_eq!(q.collect()?.column("A")?.dtype(), &DataType::Float64);
+
+    // a computed column is kept
+    let q = df
+        .lazy()
+        .select([col("A") + lit(1), col("fruits"), col("B"), col("cars")])
+        .with_projection_pushdown(false);
+    assert_eq!(count_projections(q.clone()), 1);
+    assert_eq!(
+        Vec::from(q.collect()?.column("A")?.i32()?),
+        &[Some(2), Some(3), Some(4), Some(5), Some(6)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_no_projection_of_all_columns_at_scan() -> Result<()> {
+    let df = fruits_cars();
+    let q = df
+        .clone()
+        .lazy()
+        .select([col("A"), col("fruits"), col("B"), col("cars")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(matches!(
+        lp_arena.get(lp),
+        ALogicalPlan::DataFrameScan {
+            projection: None,
+            ..
+        }
+    ));
+    assert!(q.collect()?.frame_equal(&df));
+
+    Ok(())
+}
+
+#[test]
+fn test_describe_plan_indents_inputs() -> Result<()> {
+    let df = fruits_cars();
+    let inner = df
+        .clone()
+        .lazy()
+        .inner_join(df.clone().lazy(), col("A"), col("A"));
+    let q = df.lazy().inner_join(inner, col("B"), col("B"));
+
+    let plan = q.describe_plan();
+    let indent_of = |line: &str| line.len() - line.trim_start_matches(' ').len();
+    let join_indents = plan
+        .lines()
+        .filter(|line| line.trim_start().starts_with("JOIN:"))
+        .map(indent_of)
+        .collect::<Vec<_>>();
+    assert_eq!(join_indents, &[0, 4]);
+    let scan_indents = plan
+        .lines()
+        .filter(|line| line.trim_start().starts_with("DATAFRAME"))
+        .map(indent_of)
+        .collect::<Vec<_>>();
+    assert_eq!(scan_indents, &[4, 8, 8]);
+
+    Ok(())
+}
+
+#[test]
+fn test_simplify_boolean_predicates() -> Result<()> {
+    let df = fruits_cars();
+    let e = || col("A").gt(lit(1));
+    let plan = |predicate: Expr| {
+        df.clone()
+            .lazy()
+            .filter(predicate)
+            .describe_optimized_plan()
+    };
+
+    let expected = plan(e())?;
+    assert_eq!(plan(lit(true).and(e()))?, expected);
+    assert_eq!(plan(e().and(lit(true)))?, expected);
+    assert_eq!(plan(e().or(lit(false)))?, expected);
+    assert_eq!(plan(lit(false).or(e()))?, expected);
+    assert_eq!(plan(e().not().not())?, expected);
+
+    let no_filter = df.clone().lazy().describe_optimized_plan()?;
+    assert_eq!(plan(e().or(lit(true)))?, no_filter);
+    assert_eq!(plan(lit(true).or(e()))?, no_filter);
+
+    let out = df.clone().lazy().filter(e().and(lit(false))).collect()?;
+    assert_eq!(out.height(), 0);
+    let out = df.lazy().filter(e().or(lit(false))).collect()?;
+    assert_eq!(out.height(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_redundant_null_guards_removed() -> Result<()> {
+    let df = fruits_cars();
+    let no_filter = df.clone().lazy().describe_optimized_plan()?;
+
+    let q = df.clone().lazy().filter(lit(5).is_not_null());
+    assert_eq!(q.describe_optimized_plan()?, no_filter);
+    assert_eq!(q.collect()?.height(), 5);
+
+    let q = df.clone().lazy().filter(
+        col("A")
+            .gt(lit(1))
+            .or(col("A").is_null().and(col("A").is_not_null())),
+    );
+    assert_eq!(
+        q.describe_optimized_plan()?,
+        df.clone()
+            .lazy()
+            .filter(col("A").gt(lit(1)))
+            .describe_optimized_plan()?
+    );
+    assert_eq!(q.collect()?.height(), 4);
+
+    // a filter that keeps no rows
+    let q = df
+        .lazy()
+        .filter(col("A").is_not_null().and(col("A").is_null()));
+    assert!(!q.describe_optimized_plan()?.contains("FILTER"));
+    assert_eq!(q.collect()?.height(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_redundant_sort_removed() -> Result<()> {
+    let count_sorts = |q: LazyFrame| {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+        (&lp_arena)
+            .iter(lp)
+            .filter(|(_, lp)| matches!(lp, ALogicalPlan::Sort { .. }))
+            .count()
+    };
+    let sorted = || fruits_cars().lazy().sort("B", Default::default());
+
+    // a filter and a projection keep the order
+    let q = sorted()
+        .filter(col("A").gt(lit(1)))
+        .select([col("A"), col("B")])
+        .sort("B", Default::default());
+    assert_eq!(count_sorts(q.clone()), 1);
+    let expected = sorted()
+        .filter(col("A").gt(lit(1)))
+        .select([col("A"), col("B")])
+        .collect()?;
+    assert!(q.collect()?.frame_equal(&expected));
+
+    let q = sorted().sort("B", Default::default());
+    assert_eq!(count_sorts(q), 1);
+
+    // another direction, or a sort column that is overwritten, needs another sort
+    let q = sorted().sort(
+        "B",
+        SortOptions {
+            descending: true,
+            nulls_last: false,
+        },
+    );
+    assert_eq!(count_sorts(q), 2);
+    let q = sorted()
+        .with_column((lit(10) - col("B")).alias("B"))
+        .sort("B", Default::default());
+    assert_eq!(count_sorts(q.clone()), 2);
+    assert_eq!(
+        Vec::from(q.collect()?.column("B")?.i32()?),
+        &[Some(5), Some(6), Some(7), Some(8), Some(9)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_toggle_optimizations() -> Result<()> {
+    let q = scan_foods_csv()
+        .filter(col("calories").gt(lit(50)))
+        .select([col("category"), col("calories")]);
+
+    let optimized = q.describe_optimized_plan()?;
+    assert!(optimized.contains("PROJECT 2/4 COLUMNS"));
+    assert!(!optimized.contains("FILTER"));
+
+    let unoptimized = q
+        .clone()
+        .with_projection_pushdown(false)
+        .with_predicate_pushdown(false);
+    let plan = unoptimized.describe_optimized_plan()?;
+    assert!(plan.contains("PROJECT */4 COLUMNS"));
+    assert!(plan.contains("FILTER"));
+
+    assert!(q.collect()?.frame_equal(&unoptimized.collect()?));
+    Ok(())
+}
+
+fn count_multiplications(q: LazyFrame) -> usize {
+    use crate::logical_plan::iterator::ArenaExprIter;
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+    (&lp_arena)
+        .iter(lp)
+        .flat_map(|(_, lp)| lp.get_exprs())
+        .map(|node| {
+            (&expr_arena)
+                .iter(node)
+                .filter(|(_, e)| {
+                    matches!(
+                        e,
+                        AExpr::BinaryExpr {
+                            op: Operator::Multiply,
+                            ..
+                        }
+                    )
+                })
+                .count()
+        })
+        .sum()
+}
+
+#[test]
+fn test_common_subexpr_elim() -> Result<()> {
+    let df = fruits_cars();
+    let a_times_b = || col("A") * col("B");
+
+    let q = df.clone().lazy().select([
+        a_times_b(),
+        (a_times_b() + lit(1)).alias("plus_one"),
+        col("fruits"),
+    ]);
+    assert_eq!(count_multiplications(q.clone()), 2);
+    let cse = q.clone().with_common_subexpr_elim(true);
+    assert_eq!(count_multiplications(cse.clone()), 1);
+    let out = cse.collect()?;
+    assert_eq!(out.get_column_names(), &["A", "plus_one", "fruits"]);
+    assert!(out.frame_equal(&q.collect()?));
+
+    let q = df.lazy().groupby_stable([col("fruits")]).agg([
+        a_times_b().sum().alias("sum"),
+        a_times_b().max(),
+        col("B").min(),
+    ]);
+    assert_eq!(count_multiplications(q.clone()), 2);
+    let cse = q.clone().with_common_subexpr_elim(true);
+    assert_eq!(count_multiplications(cse.clone()), 1);
+    let out = cse.collect()?;
+    assert_eq!(out.get_column_names(), &["fruits", "sum", "A", "B"]);
+    assert!(out.frame_equal(&q.collect()?));
+
+    Ok(())
+}
+
+#[test]
+fn test_common_subplan_elim() -> Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let runs = Arc::new(AtomicUsize::new(0));
+    let runs_udf = runs.clone();
+
+    let q = fruits_cars()
+        .lazy()
+        .map(
+            move |df| {
+                runs_udf.fetch_add(1, Ordering::Relaxed);
+                Ok(df)
+            },
+            None,
+            None,
+            None,
+        )
+        .sort("A", Default::default());
+    let q = q.clone().inner_join(q, col("A"), col("A"));
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let caches = (&lp_arena)
+        .iter(lp)
+        .filter(|(_, lp)| matches!(lp, ALogicalPlan::Cache { .. }))
+        .count();
+    assert_eq!(caches, 2);
+
+    let out = q.clone().collect()?;
+    assert_eq!(out.height(), 5);
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    let out_no_cache = q.with_common_subplan_elim(false).collect()?;
+    assert!(out.frame_equal(&out_no_cache));
+    assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_common_subplan_sorts_on_different_columns() -> Result<()> {
+    let lf = fruits_cars().lazy();
+    let by_a = lf.clone().sort("A", Default::default());
+    let by_b = lf.sort("B", Default::default());
+
+    let q = concat([by_a.clone(), by_b.clone()], false)?;
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(!(&lp_arena)
+        .iter(lp)
+        .any(|(_, lp)| matches!(lp, ALogicalPlan::Cache { .. })));
+
+    let out = q.collect()?;
+    let a = out
+        .column("A")?
+        .i32()?
+        .into_no_null_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(a, &[1, 2, 3, 4, 5, 5, 4, 3, 2, 1]);
+
+    let out = by_a
+        .inner_join(by_b, col("fruits"), col("fruits"))
+        .collect()?;
+    let expected = fruits_cars()
+        .lazy()
+        .sort("A", Default::default())
+        .inner_join(
+            fruits_cars().lazy().sort("B", Default::default()),
+            col("fruits"),
+            col("fruits"),
+        )
+        .collect()?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_stops_pushdown() -> Result<()> {
+    let q = fruits_cars().lazy().cache();
+    let out = q
+        .clone()
+        .filter(col("A").gt(lit(2)))
+        .inner_join(q.filter(col("A").lt(lit(4))), col("A"), col("A"))
+        .collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(3)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_common_subexpr_elim_strings() -> Result<()> {
+    use crate::logical_plan::iterator::ArenaExprIter;
+    let s = || col("A").cast(DataType::Utf8) + lit("-suffix");
+    let q = fruits_cars().lazy().select([
+        s().alias("x"),
+        s().eq(lit("1-suffix")).alias("y"),
+        s().lt(lit("3")).alias("z"),
+    ]);
+
+    let count_casts = |q: LazyFrame| {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+        (&lp_arena)
+            .iter(lp)
+            .flat_map(|(_, lp)| lp.get_exprs())
+            .map(|node| {
+                (&expr_arena)
+                    .iter(node)
+                    .filter(|(_, e)| matches!(e, AExpr::Cast { .. }))
+                    .count()
+            })
+            .sum::<usize>()
+    };
+    assert_eq!(count_casts(q.clone()), 3);
+    let cse = q.clone().with_common_subexpr_elim(true);
+    assert_eq!(count_casts(cse.clone()), 1);
+
+    let out = cse.collect()?;
+    assert_eq!(out.get_column_names(), &["x", "y", "z"]);
+    assert!(out.frame_equal(&q.collect()?));
+    assert_eq!(out.column("x")?.utf8()?.get(0), Some("1-suffix"));
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_optimization() -> Result<()> {
+    /// Drop the filter `x > 0`.
+    struct RemoveXPositive;
+
+    impl LogicalPlanRewriter for RemoveXPositive {
+        fn rewrite_expr(&mut self, expr: Expr) -> Result<Expr> {
+            if expr == col("x").gt(lit(0)) {
+                Ok(lit(true))
+            } else {
+                Ok(expr)
+            }
+        }
+    }
+
+    impl Optimize for RemoveXPositive {
+        fn optimize(&self, logical_plan: LogicalPlan) -> Result<LogicalPlan> {
+            RemoveXPositive.rewrite_plan(logical_plan)
+        }
+    }
+
+    let df = df![
+        "x" => [-1, 1, 2],
+        "y" => [1, 2, 3]
+    ]?;
+    let q = df.lazy().filter(col("x").gt(lit(0))).select([col("y")]);
+    assert_eq!(q.clone().collect()?.height(), 2);
+
+    let out = q.with_optimization(Box::new(RemoveXPositive)).collect()?;
+    assert_eq!(out.get_column_names(), &["y"]);
+    assert_eq!(out.height(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_nested_node() -> Result<()> {
+    /// Scan another frame with the same schema.
+    struct ReplaceScan(DataFrame);
+
+    impl LogicalPlanRewriter for ReplaceScan {
+        fn rewrite_node(&mut self, lp: LogicalPlan) -> Result<LogicalPlan> {
+            match lp {
+                LogicalPlan::DataFrameScan { .. } => Ok(self.0.clone().lazy().logical_plan),
+                lp => Ok(lp),
+            }
+        }
+    }
+
+    let df = df![
+        "x" => [-1, 1, 2],
+        "y" => [1, 2, 3]
+    ]?;
+    let other = df![
+        "x" => [3, -4, 5],
+        "y" => [4, 5, 6]
+    ]?;
+    // the scan is below the filter and the projection
+    let q = df.lazy().filter(col("x").gt(lit(0))).select([col("y")]);
+
+    let lp = ReplaceScan(other).rewrite_plan(q.logical_plan)?;
+    let out = LazyFrame::from(lp).collect()?;
+    let expected = df![
+        "y" => [4, 6]
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_trace_optimizations() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .filter(col("A").gt(lit(1)))
+        .select([col("A"), col("B")]);
+
+    let trace = q.trace_optimizations()?;
+    let passes = trace
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    // the pushdowns are repeated until the plan doesn't change
+    let (first, rest) = passes.split_first().unwrap();
+    let (pushdowns, last) = rest.split_at(rest.len() - 2);
+    assert_eq!(*first, "simplify_expr");
+    assert!(pushdowns.len() >= 2);
+    assert!(pushdowns
+        .chunks(2)
+        .all(|pair| pair == ["projection_pushdown", "predicate_pushdown"]));
+    assert_eq!(last, &["slice_pushdown", "optimization_rules"]);
+    assert_eq!(trace.last().unwrap().1, q.describe_optimized_plan()?);
+    assert_eq!(q.explain(true)?, q.describe_optimized_plan()?);
+    assert_eq!(q.explain(false)?, q.describe_plan());
+
+    // disabled passes are not traced
+    let trace = q.with_predicate_pushdown(false).trace_optimizations()?;
+    assert!(trace.iter().all(|(name, _)| name != "predicate_pushdown"));
+
+    Ok(())
+}
@@ -159,6 +159,32 @@ fn test_strict_cast_predicate_pushdown() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cast_predicate_pushdown() -> Result<()> {
+    let df = df![
+        "x" => [0.5, 1.7, 2.2, 3.9],
+        "y" => [1, 2, 3, 4],
+    ]?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .filter(col("x").cast(DataType::Int32).gt_eq(lit(2)));
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("y")?.i32()?), &[Some(3), Some(4)]);
+
+    let out = df
+        .lazy()
+        .with_column(col("x").cast(DataType::Int32).alias("x_int"))
+        .filter(col("x_int").eq(lit(1)))
+        .collect()?;
+    assert_eq!(out.column("x_int")?.dtype(), &DataType::Int32);
+    assert_eq!(Vec::from(out.column("y")?.i32()?), &[Some(2)]);
+
+    Ok(())
+}
+
 #[test]
 fn test_filter_nulls_created_by_join() -> Result<()> {
     // #2602
@@ -197,6 +223,73 @@ fn test_filter_nulls_created_by_join() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_null_checks_pushdown_over_left_join() -> Result<()> {
+    let a = df![
+        "key" => ["foo", "bar", "ham"],
+        "a" => [Some(1), None, Some(3)]
+    ]?
+    .lazy();
+    let b = df![
+        "key" => ["bar", "ham"],
+        "b" => [Some(1), None]
+    ]?
+    .lazy();
+
+    // the left table is not changed by the join
+    let q = a
+        .clone()
+        .left_join(b.clone(), col("key"), col("key"))
+        .filter(col("a").is_null());
+    assert_eq!(predicates_at_scans(q.clone()), 1);
+    let out = q.collect()?;
+    assert_eq!(out.column("key")?, &Series::new("key", &["bar"]));
+
+    // the join creates nulls in the right table, so the predicate stays on top of the join
+    let q = a
+        .left_join(b, col("key"), col("key"))
+        .filter(col("b").is_null());
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.column("key")?, &Series::new("key", &["foo", "ham"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_null_checks_pushed_to_scan() -> Result<()> {
+    let df = df![
+        "nulls" => [None::<i32>, None, None],
+        "values" => [1, 2, 3]
+    ]?;
+
+    let q = df
+        .clone()
+        .lazy()
+        .select([col("nulls").is_null(), col("values").is_not_null()]);
+    let schema = q.schema();
+    assert_eq!(schema.get("nulls"), Some(&DataType::Boolean));
+    assert_eq!(schema.get("values"), Some(&DataType::Boolean));
+
+    let q = df.clone().lazy().filter(col("nulls").is_not_null());
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 0);
+
+    let q = df.clone().lazy().filter(col("nulls").is_null());
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 3);
+
+    let q = df.clone().lazy().filter(col("values").is_null());
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 0);
+
+    let q = df.lazy().filter(col("values").is_not_null());
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 3);
+
+    Ok(())
+}
+
 #[test]
 fn test_filter_null_creation_by_cast() -> Result<()> {
     let df = df![
@@ -218,3 +311,652 @@ fn test_filter_null_creation_by_cast() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_predicates_on_same_roots_combined() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3, 4],
+        "b" => [4, 3, 2, 1]
+    ]?;
+
+    // both predicates refer to the columns `a` and `b`, but in a different order
+    let q = df
+        .lazy()
+        .filter(col("a").gt(col("b")))
+        .filter(col("b").gt_eq(col("a") - lit(2)));
+
+    assert!(predicate_at_scan(q.clone()));
+
+    let out = q.collect()?;
+    let expected = df![
+        "a" => [3],
+        "b" => [2]
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_two_root_predicate_sort_and_join() -> Result<()> {
+    let left = df![
+        "key" => [1, 2, 3, 4],
+        "a" => [1, 5, 3, 7],
+        "b" => [2, 4, 6, 1]
+    ]?;
+    let right = df![
+        "key" => [1, 2, 3, 4],
+        "c" => [0, 3, 2, 8]
+    ]?;
+
+    // the predicate may pass the sort
+    let q = left
+        .clone()
+        .lazy()
+        .sort("a", Default::default())
+        .filter(col("a").gt(col("b")));
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2), Some(4)]);
+
+    // `a > b` can be pushed to the left table, `a > c` must be done after the join
+    let q = left
+        .lazy()
+        .inner_join(right.lazy(), col("key"), col("key"))
+        .filter(col("a").gt(col("b")))
+        .filter(col("a").gt(col("c")));
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_chained_filters_same_column() -> Result<()> {
+    let df = df![
+        "x" => [0, 1, 2, 5, 10, 11],
+    ]?;
+
+    let q = df
+        .lazy()
+        .filter(col("x").gt(lit(1)))
+        .filter(col("x").lt(lit(10)));
+
+    let out = q.clone().collect()?;
+    let expected = q
+        .with_predicate_pushdown(false)
+        .with_simplify_expr(false)
+        .collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(Vec::from(out.column("x")?.i32()?), &[Some(2), Some(5)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_on_groupby_key_pushdown() -> Result<()> {
+    let df = fruits_cars();
+
+    let q = df
+        .lazy()
+        .groupby_stable([col("fruits")])
+        .agg([col("B").sum()])
+        .filter(col("fruits").eq(lit("banana")));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.shape(), (1, 2));
+    let b = out.column("B")?.cast(&DataType::Int64)?;
+    assert_eq!(Vec::from(b.i64()?), &[Some(10)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_conjunctive_predicate_split_over_join() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3, 4],
+        "a" => [0, 2, 4, 6],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 3, 4],
+        "b" => [1, 3, 5, 7],
+    ]?;
+
+    let q = df_a
+        .lazy()
+        .inner_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("a").gt(lit(1)).and(col("b").lt(lit(6))));
+
+    assert_eq!(predicates_at_scans(q.clone()), 2);
+
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2), Some(3)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_disjunctive_predicate_kept_whole_over_join() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3, 4],
+        "a" => [0, 2, 4, 6],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 3, 4],
+        "b" => [1, 3, 5, 7],
+    ]?;
+
+    // an `OR` over both sides can only be evaluated after the join
+    let q = df_a
+        .clone()
+        .lazy()
+        .inner_join(df_b.clone().lazy(), col("key"), col("key"))
+        .filter(col("a").gt(lit(4)).or(col("b").lt(lit(2))));
+    assert_eq!(predicates_at_scans(q.clone()), 0);
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(1), Some(4)]);
+
+    // an `OR` over one side is pushed down as a whole
+    let q = df_a
+        .lazy()
+        .inner_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("a").gt(lit(4)).or(col("a").lt(lit(1))));
+    assert_eq!(predicates_at_scans(q.clone()), 1);
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(1), Some(4)]);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "cross_join")]
+fn test_predicate_pushdown_over_cross_join() -> Result<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+    ]?;
+    let right = df![
+        "b" => [1, 2, 3, 4],
+    ]?;
+
+    // predicates on a single side are pushed to that side
+    let q = left
+        .clone()
+        .lazy()
+        .cross_join(right.clone().lazy())
+        .filter(col("a").gt(lit(1)))
+        .filter(col("b").lt(lit(4)));
+    assert_eq!(predicates_at_scans(q.clone()), 2);
+    assert_eq!(q.collect()?.shape(), (2 * 3, 2));
+
+    // a predicate over both sides stays above the join
+    let q = left
+        .lazy()
+        .cross_join(right.lazy())
+        .filter(col("a").eq(col("b")));
+    assert_eq!(predicates_at_scans(q.clone()), 0);
+    assert_eq!(q.collect()?.shape(), (3, 2));
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_on_join_key_pushed_to_both_sides() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3, 4],
+        "v" => [10, 20, 30, 40],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 3, 4],
+        "v" => [40, 30, 20, 10],
+    ]?;
+
+    let q = df_a
+        .lazy()
+        .inner_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("key").gt(lit(1)))
+        // `v` refers to the left table, the right one is renamed to `v_right`
+        .filter(col("v").lt(lit(40)));
+
+    assert_eq!(predicates_at_scans(q.clone()), 2);
+
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2), Some(3)]);
+    assert_eq!(
+        Vec::from(out.column("v_right")?.i32()?),
+        &[Some(30), Some(20)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_right_predicate_local_for_left_join() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3],
+        "a" => [1, 2, 3],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 4],
+        "b" => [1, 5, 10],
+    ]?;
+
+    let q = df_a
+        .lazy()
+        .left_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("b").lt(lit(3)));
+
+    // filtering the right table would change the rows that have a match
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal_missing(&expected));
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(1)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicates_local_for_outer_join() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3],
+        "a" => [1, 2, 3],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 4],
+        "b" => [1, 5, 10],
+    ]?;
+
+    let q = df_a
+        .lazy()
+        .outer_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("a").gt(lit(1)))
+        .filter(col("b").gt(lit(1)));
+
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal_missing(&expected));
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_on_suffixed_join_column() -> Result<()> {
+    let df_a = df![
+        "key" => [1, 2, 3],
+        "temp" => [10, 20, 30],
+    ]?;
+    let df_b = df![
+        "key" => [1, 2, 3],
+        "temp" => [30, 20, 10],
+        "b_right" => [1, 2, 3],
+    ]?;
+
+    // `temp_right` is renamed back to `temp` and pushed to the right table
+    let q = df_a
+        .clone()
+        .lazy()
+        .inner_join(df_b.clone().lazy(), col("key"), col("key"))
+        .filter(col("temp_right").gt(lit(15)));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(1), Some(2)]);
+
+    // `b_right` is the name in the source and must not be rewritten
+    let q = df_a
+        .lazy()
+        .inner_join(df_b.lazy(), col("key"), col("key"))
+        .filter(col("b_right").gt(lit(2)));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(3)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_pushdown_into_csv_scan() -> Result<()> {
+    let q = scan_foods_csv().filter(col("calories").gt(lit(100)));
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert!(out.height() > 0);
+
+    // a predicate on a column that is not in the csv is done after the scan
+    let q = scan_foods_csv()
+        .with_column((col("calories") * lit(2)).alias("double"))
+        .filter(col("double").gt(lit(200)));
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.clone().collect()?;
+    let expected = q.with_predicate_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_on_aggregation_not_pushed() -> Result<()> {
+    let df = df![
+        "groups" => ["a", "a", "b"],
+        "values" => [4, 4, 1],
+    ]?;
+
+    // this filters on the aggregated values, it cannot be done before the groupby
+    let q = df
+        .lazy()
+        .groupby_stable([col("groups")])
+        .agg([col("values").sum()])
+        .filter(col("values").gt(lit(5)));
+
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("groups")?.utf8()?), &[Some("a")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_on_computed_alias_not_pushed() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3, 4],
+    ]?;
+
+    let q = df
+        .lazy()
+        .select([col("a").shift(1).alias("b")])
+        .filter(col("b").gt(lit(1)));
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("b")?.i32()?), &[Some(2), Some(3)]);
+
+    // the predicate cannot be rewritten to a predicate on `a`
+    let df = df![
+        "a" => [Some(1), None, Some(3)],
+    ]?;
+    let q = df
+        .lazy()
+        .select([col("a").is_not_null().alias("b")])
+        .filter(col("b"));
+    assert!(!predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.height(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_pushdown_multi_column_sort() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 1, 2, 3],
+        "b" => [5, 4, 3, 2, 1],
+    ]?;
+
+    let q = df
+        .lazy()
+        .sort_by_exprs([col("a"), col("b")], vec![false, true])
+        .filter(col("a").lt(lit(3)));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.i32()?),
+        &[Some(1), Some(1), Some(2), Some(2)]
+    );
+    assert_eq!(
+        Vec::from(out.column("b")?.i32()?),
+        &[Some(5), Some(3), Some(4), Some(2)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stacked_filters() -> Result<()> {
+    let mut q = fruits_cars().lazy();
+    for i in 0..100 {
+        q = q.filter(col("A").gt(lit(i % 2)));
+    }
+    let q = q
+        .filter(col("B").gt(lit(1)))
+        .filter(col("fruits").eq(lit("banana")))
+        .select([col("cars")]);
+
+    assert_eq!(predicates_at_scans(q.clone()), 1);
+    let out = q.collect()?;
+    assert_eq!(out.column("cars")?, &Series::new("cars", &["audi"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_order_is_deterministic() -> Result<()> {
+    // the predicates above the slice stay local and are combined in one filter, the predicates
+    // below the slice are pushed down to the scan
+    let q = fruits_cars()
+        .lazy()
+        .filter(col("A").gt(lit(1)))
+        .filter(col("B").gt(lit(1)))
+        .filter(col("fruits").eq(lit("banana")))
+        .slice(0, 4)
+        .filter(col("A").lt(lit(5)))
+        .filter(col("B").lt(lit(5)))
+        .filter(col("cars").eq(lit("beetle")));
+
+    let plan = q.describe_optimized_plan()?;
+    for _ in 0..10 {
+        assert_eq!(q.describe_optimized_plan()?, plan);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_pushdown_over_unique() -> Result<()> {
+    let df = df![
+        "a" => [1, 1, 2, 2],
+        "b" => [1, 2, 1, 2]
+    ]?;
+
+    // without a subset, any element wise predicate can be done first
+    let q = df
+        .clone()
+        .lazy()
+        .unique_stable(None, UniqueKeepStrategy::First)
+        .filter(col("b").eq(lit(2)));
+    assert!(predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 2);
+
+    // a predicate on the subset can be done first
+    let q = df
+        .clone()
+        .lazy()
+        .unique_stable(Some(vec!["a".into()]), UniqueKeepStrategy::First)
+        .filter(col("a").eq(lit(2)));
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("b")?.i32()?), &[Some(1)]);
+
+    // a predicate on another column must be done after dropping the duplicates
+    let q = df
+        .lazy()
+        .unique_stable(Some(vec!["a".into()]), UniqueKeepStrategy::First)
+        .filter(col("b").eq(lit(2)));
+    assert!(!predicate_at_scan(q.clone()));
+    assert_eq!(q.collect()?.height(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_ternary_pushdown_through_join() -> Result<()> {
+    let left = df![
+        "key" => [1, 2, 3, 4],
+        "a" => [1, 2, 3, 4],
+        "x" => [0, 0, 0, 0],
+    ]?;
+    let right = df![
+        "key" => [1, 2, 3, 4],
+        "b" => [4, 3, 2, 1],
+        "y" => [0, 0, 0, 0],
+    ]?;
+    let joined = || {
+        left.clone()
+            .lazy()
+            .inner_join(right.clone().lazy(), col("key"), col("key"))
+    };
+    let keys = |q: LazyFrame| -> Result<Vec<Option<i32>>> {
+        let out = q.sort("key", Default::default()).collect()?;
+        Ok(Vec::from(out.column("key")?.i32()?))
+    };
+
+    // all branches use columns of the left table
+    let q = joined().filter(
+        when(col("a").gt(lit(1)))
+            .then(col("a"))
+            .otherwise(lit(0))
+            .gt(lit(2)),
+    );
+    assert_eq!(predicates_at_scans(q.clone()), 1);
+    assert_eq!(keys(q)?, &[Some(3), Some(4)]);
+
+    // the branches use columns of both tables
+    let q = joined().filter(
+        when(col("a").gt(col("b")))
+            .then(col("a"))
+            .otherwise(col("b"))
+            .gt(lit(3)),
+    );
+    assert_eq!(predicates_at_scans(q.clone()), 0);
+    assert_eq!(keys(q)?, &[Some(1), Some(4)]);
+
+    // the columns of all branches are projected
+    let q = joined().select([
+        col("key"),
+        when(col("a").gt(col("b")))
+            .then(col("a"))
+            .otherwise(col("b"))
+            .alias("max"),
+    ]);
+    let out = q.clone().collect()?;
+    assert_eq!(out.get_column_names(), &["key", "max"]);
+    assert!(out.frame_equal(&q.with_projection_pushdown(false).collect()?));
+
+    Ok(())
+}
+
+#[test]
+fn test_expensive_predicate_after_scan() -> Result<()> {
+    use crate::logical_plan::conversion::node_to_expr;
+
+    let q = fruits_cars()
+        .lazy()
+        .filter(col("fruits").eq(lit("banana")))
+        .filter(col("A").gt(lit(2)));
+
+    // the integer predicate is done by the scan, the string predicate only on the rows that pass
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let (input, predicate) = match lp_arena.get(lp) {
+        ALogicalPlan::Selection { input, predicate } => (*input, *predicate),
+        _ => panic!("expected a selection"),
+    };
+    assert_eq!(
+        node_to_expr(predicate, &expr_arena),
+        col("fruits").eq(lit("banana"))
+    );
+    assert!(matches!(
+        lp_arena.get(input),
+        ALogicalPlan::DataFrameScan {
+            selection: Some(_),
+            ..
+        }
+    ));
+
+    let out = q.collect()?;
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(5)]);
+
+    // the predicates of a filter that is not pushed down are split too, and not combined again
+    let q = fruits_cars()
+        .lazy()
+        .groupby_stable([col("fruits")])
+        .agg([col("A").sum(), col("cars").first()])
+        .filter(col("cars").eq(lit("beetle")).and(col("A").gt(lit(7))));
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let (input, predicate) = match lp_arena.get(lp) {
+        ALogicalPlan::Selection { input, predicate } => (*input, *predicate),
+        _ => panic!("expected a selection"),
+    };
+    assert_eq!(
+        node_to_expr(predicate, &expr_arena),
+        col("cars").eq(lit("beetle"))
+    );
+    assert!(matches!(
+        lp_arena.get(input),
+        ALogicalPlan::Selection { .. }
+    ));
+    let out = q.collect()?;
+    assert_eq!(out.column("fruits")?, &Series::new("fruits", &["banana"]));
+
+    // the predicate of a file scan is kept whole
+    let q = scan_foods_csv().filter(
+        col("category")
+            .eq(lit("vegetables"))
+            .and(col("calories").gt(lit(20))),
+    );
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    assert!(matches!(
+        lp_arena.get(lp),
+        ALogicalPlan::CsvScan {
+            predicate: Some(_),
+            ..
+        }
+    ));
+    let out = q.collect()?;
+    assert_eq!(out.height(), 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_deep_selection_chain() -> Result<()> {
+    use crate::logical_plan::optimizer::{
+        predicate_pushdown::PredicatePushDown, projection_pushdown::ProjectionPushDown,
+    };
+
+    // the conversion to the arena and the pushdowns walk a chain of selections in a loop. The
+    // plan is built by hand, as the builder takes the schema of its input recursively.
+    let mut lp = fruits_cars().lazy().logical_plan;
+    for i in 0..50_000 {
+        lp = LogicalPlan::Selection {
+            input: Box::new(lp),
+            predicate: col("A").gt(lit(i % 2)),
+        };
+    }
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let root = to_alp(lp, &mut expr_arena, &mut lp_arena)?;
+    let alp = lp_arena.take(root);
+    let alp = ProjectionPushDown {}.optimize(alp, &mut lp_arena, &mut expr_arena)?;
+    let alp = PredicatePushDown::default().optimize(alp, &mut lp_arena, &mut expr_arena)?;
+    assert!(matches!(
+        alp,
+        ALogicalPlan::DataFrameScan {
+            selection: Some(_),
+            ..
+        }
+    ));
+
+    Ok(())
+}
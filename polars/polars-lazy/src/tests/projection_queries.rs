@@ -1,4 +1,5 @@
 use super::*;
+use crate::utils::aexpr_to_root_names;
 
 #[test]
 fn test_join_suffix_and_drop() -> Result<()> {
@@ -34,6 +35,196 @@ fn test_join_suffix_and_drop() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_join_suffix_projection_pd() -> Result<()> {
+    let left = df![
+        "key" => [1, 2, 3],
+        "v" => [10, 20, 30],
+        "unused" => [0, 0, 0],
+    ]?;
+    let right = df![
+        "key" => [1, 2, 3],
+        "v" => [30, 20, 10],
+    ]?;
+
+    let out = left
+        .lazy()
+        .join_builder()
+        .with(right.lazy())
+        .left_on([col("key")])
+        .right_on([col("key")])
+        .suffix("_other")
+        .finish()
+        .select([col("v_other"), col("key")])
+        .collect()?;
+
+    let expected = df![
+        "v_other" => [30, 20, 10],
+        "key" => [1, 2, 3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_join_three_tables_with_suffixes() -> Result<()> {
+    let table = |values: [i32; 3]| {
+        df![
+            "key" => [1, 2, 3],
+            "value" => values,
+        ]
+        .map(|df| df.lazy())
+    };
+
+    let q = table([10, 20, 30])?
+        .join_builder()
+        .with(table([1, 2, 3])?)
+        .left_on([col("key")])
+        .right_on([col("key")])
+        .suffix("_b")
+        .finish()
+        .join_builder()
+        .with(table([-1, -2, -3])?)
+        .left_on([col("key")])
+        .right_on([col("key")])
+        .suffix("_c")
+        .finish()
+        .filter(col("value_c").lt(lit(-1)))
+        .select([col("value_c"), col("value_b"), col("key")]);
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    let expected = df![
+        "value_c" => [-2, -3],
+        "value_b" => [2, 3],
+        "key" => [2, 3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_join_alias_projection_pd() -> Result<()> {
+    let left = df![
+        "days" => [1, 2, 3],
+        "temp" => [10, 20, 30],
+        "rain" => [0.1, 0.2, 0.3],
+    ]?;
+    let right = df![
+        "days" => [1, 2, 3],
+        "temp" => [11, 21, 31],
+        "wind" => [5, 6, 7],
+    ]?;
+
+    let q = left
+        .lazy()
+        .inner_join(right.lazy(), col("days"), col("days"));
+    let out = q
+        .clone()
+        .select([
+            col("temp").alias("temp_a"),
+            col("temp_right").alias("temp_b"),
+            col("days").alias("day"),
+        ])
+        .collect()?;
+
+    let expected = df![
+        "temp_a" => [10, 20, 30],
+        "temp_b" => [11, 21, 31],
+        "day" => [1, 2, 3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    // without the left column the join doesn't add the suffix
+    let out = q
+        .clone()
+        .select([col("temp_right").alias("temp_b")])
+        .collect()?;
+    let expected = df![
+        "temp_b" => [11, 21, 31],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    let out = q.select([col("temp_right") * lit(2)]).collect()?;
+    let expected = df![
+        "temp_right" => [22, 42, 62],
+    ]?;
+    assert!(out.frame_equal(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_join_key_selected_pd() -> Result<()> {
+    let left = df![
+        "days" => [1, 2, 3],
+        "temp" => [10, 20, 30],
+        "rain" => [0.1, 0.2, 0.3],
+    ]?;
+    let right = df![
+        "days" => [1, 2, 3],
+        "wind" => [5, 6, 7],
+    ]?;
+
+    // the join key is pushed down for the join and for the selection, but only projected once
+    let out = left
+        .lazy()
+        .left_join(right.lazy(), col("days"), col("days"))
+        .select([col("days"), col("temp")])
+        .collect()?;
+
+    let expected = df![
+        "days" => [1, 2, 3],
+        "temp" => [10, 20, 30],
+    ]?;
+    assert!(out.frame_equal(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_join_right_column_with_suffix_pd() -> Result<()> {
+    let left = df![
+        "key" => [1, 2, 3],
+        "foo" => [10, 20, 30],
+    ]?;
+    let right = df![
+        "key" => [1, 2, 3],
+        "foo_right" => [100, 200, 300],
+        "bar" => [0, 0, 0],
+    ]?;
+
+    // the right table has a column that is named like a suffixed column
+    let q = left
+        .clone()
+        .lazy()
+        .inner_join(right.lazy(), col("key"), col("key"))
+        .select([col("foo_right"), col("key")]);
+    let out = q.clone().collect()?;
+    let expected = df![
+        "foo_right" => [100, 200, 300],
+        "key" => [1, 2, 3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+    assert!(out.frame_equal(&q.with_projection_pushdown(false).collect()?));
+
+    // only the suffixed column of the right table is projected
+    let right = df![
+        "key" => [1, 2, 3],
+        "foo" => [-1, -2, -3],
+    ]?;
+    let out = left
+        .lazy()
+        .inner_join(right.lazy(), col("key"), col("key"))
+        .select([col("foo_right")])
+        .collect()?;
+    let expected = df![
+        "foo_right" => [-1, -2, -3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "cross_join")]
 fn test_cross_join_pd() -> Result<()> {
@@ -105,3 +296,606 @@ fn scan_join_same_file() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_projection_of_alias_pd() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => [3, 2, 1],
+    ]?;
+
+    let out = df
+        .lazy()
+        .select([col("a").alias("x")])
+        .select([col("x")])
+        .collect()?;
+
+    let expected = df![
+        "x" => [1, 2, 3],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_projection_pushdown_into_csv_scan() -> Result<()> {
+    let q = scan_foods_csv().select([col("calories")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena).iter(lp).find_map(|(_, lp)| match lp {
+        ALogicalPlan::CsvScan { options, .. } => Some(options.with_columns.clone()),
+        _ => None,
+    });
+    assert_eq!(with_columns, Some(Some(vec!["calories".to_string()])));
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["calories"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_csv_scan_projection_order() -> Result<()> {
+    let out = scan_foods_csv()
+        .select([col("sugars_g"), col("category")])
+        .collect()?;
+    assert_eq!(out.get_column_names(), &["sugars_g", "category"]);
+    assert_eq!(out.shape(), (27, 2));
+
+    // a non-trivial expression is projected on top of the scan
+    let out = scan_foods_csv()
+        .select([(col("fats_g") * lit(2.0)).alias("fats_x2"), col("category")])
+        .collect()?;
+    assert_eq!(out.get_column_names(), &["fats_x2", "category"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_projection_order_through_pushdown() -> Result<()> {
+    let df = fruits_cars();
+    let reversed = ["cars", "B", "fruits", "A"];
+
+    // the projections are split over the `with_column` and the sort
+    let q = df
+        .clone()
+        .lazy()
+        .with_column((col("A") * lit(2)).alias("A2"))
+        .sort("B", Default::default())
+        .select(reversed.iter().map(|name| col(name)).collect::<Vec<_>>());
+    assert_eq!(q.schema().iter_names().collect::<Vec<_>>(), &reversed);
+    assert_eq!(q.collect()?.get_column_names(), &reversed);
+
+    let other = df![
+        "A" => [1, 2, 3, 4, 5],
+        "C" => [1, 1, 1, 1, 1],
+    ]?;
+    let q = df
+        .lazy()
+        .inner_join(other.lazy(), col("A"), col("A"))
+        .select([col("C"), col("cars"), col("A")]);
+    assert_eq!(q.collect()?.get_column_names(), &["C", "cars", "A"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_reversed_projection_order_across_join() -> Result<()> {
+    let left = df![
+        "a" => [1, 2, 3],
+        "b" => [4, 5, 6],
+        "c" => [7, 8, 9],
+    ]?;
+    let right = df![
+        "a" => [1, 2, 3],
+        "b" => [-4, -5, -6],
+        "d" => [0, 1, 0],
+    ]?;
+    let reversed = ["d", "b_right", "c", "b", "a"];
+
+    let q = left
+        .lazy()
+        .inner_join(right.lazy(), col("a"), col("a"))
+        .select(reversed.iter().map(|name| col(name)).collect::<Vec<_>>())
+        .filter(col("a").gt(lit(1)));
+    let out = q.clone().collect()?;
+    assert_eq!(out.get_column_names(), &reversed);
+    assert!(out.frame_equal(&q.with_projection_pushdown(false).collect()?));
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_select_no_duplicate_projections() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), col("B"), col("fruits")])
+        .select([col("A"), col("B")])
+        .select([col("A"), (col("A") + col("B")).alias("C")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let projection = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::DataFrameScan { projection, .. } => projection.clone(),
+            _ => None,
+        })
+        .unwrap();
+    let mut names = projection
+        .iter()
+        .flat_map(|node| aexpr_to_root_names(*node, &expr_arena))
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, &[Arc::from("A"), Arc::from("B")]);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["A", "C"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_repeated_select_projects_once() -> Result<()> {
+    let scan_projection = |q: LazyFrame| -> Result<Vec<Arc<str>>> {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena)?;
+        let projection = (&lp_arena)
+            .iter(lp)
+            .find_map(|(_, lp)| match lp {
+                ALogicalPlan::DataFrameScan { projection, .. } => projection.clone(),
+                _ => None,
+            })
+            .unwrap();
+        Ok(projection
+            .iter()
+            .flat_map(|node| aexpr_to_root_names(*node, &expr_arena))
+            .collect())
+    };
+
+    let q = fruits_cars().lazy().select([col("A")]).select([col("A")]);
+    assert_eq!(scan_projection(q.clone())?, &[Arc::from("A")]);
+    assert_eq!(q.collect()?.get_column_names(), &["A"]);
+
+    // aliases of the same column are kept, the column is projected once
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), col("A").alias("A2")])
+        .select([col("A2"), col("A")]);
+    assert_eq!(scan_projection(q.clone())?, &[Arc::from("A")]);
+    assert_eq!(q.collect()?.get_column_names(), &["A2", "A"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_literal_projection_pd() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A"), lit(1i64).alias("one")])
+        .filter(col("A").gt(lit(3)));
+
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["A", "one"]);
+    assert_eq!(out.column("one")?.dtype(), &DataType::Int64);
+    assert_eq!(Vec::from(out.column("A")?.i32()?), &[Some(4), Some(5)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_column_not_selected_csv_pd() -> Result<()> {
+    let q = scan_foods_csv()
+        .filter(col("fats_g").gt(lit(1)))
+        .select([col("category")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let mut with_columns = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+            _ => None,
+        })
+        .unwrap();
+    with_columns.sort_unstable();
+    assert_eq!(with_columns, &["category", "fats_g"]);
+    assert!(predicate_at_scan(q.clone()));
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["category"]);
+    let expected = scan_foods_csv()
+        .collect()?
+        .lazy()
+        .filter(col("fats_g").gt(lit(1)))
+        .select([col("category")])
+        .collect()?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_count_projection_pd() -> Result<()> {
+    let q = scan_foods_csv().select([count()]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(with_columns, &["calories"]);
+
+    let get_count =
+        |out: DataFrame| -> Result<Option<IdxSize>> { Ok(out.column("count")?.idx()?.get(0)) };
+    let height = scan_foods_csv().collect()?.height();
+    assert_eq!(get_count(q.collect()?)?, Some(height as IdxSize));
+
+    let out = fruits_cars()
+        .lazy()
+        .filter(col("fruits").eq(lit("banana")))
+        .select([count()])
+        .collect()?;
+    assert_eq!(get_count(out)?, Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn test_count_unique_projection_pd() -> Result<()> {
+    // the rows are only duplicated in some of the columns
+    let df = df![
+        "a" => [1, 1, 2, 2],
+        "b" => [1, 2, 3, 3],
+        "c" => ["x", "y", "z", "z"]
+    ]?;
+
+    let out = df
+        .clone()
+        .lazy()
+        .unique(None, UniqueKeepStrategy::First)
+        .count()
+        .collect()?;
+    assert_eq!(out.column("count")?.idx()?.get(0), Some(3));
+
+    let out = df
+        .lazy()
+        .unique(None, UniqueKeepStrategy::First)
+        .select([col("a")])
+        .collect()?;
+    assert_eq!(out.height(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_lazy_count() -> Result<()> {
+    let q = scan_foods_csv().count();
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(with_columns.len(), 1);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["count"]);
+    let height = scan_foods_csv().collect()?.height();
+    assert_eq!(out.column("count")?.idx()?.get(0), Some(height as IdxSize));
+
+    Ok(())
+}
+
+#[test]
+fn test_sort_column_not_selected() -> Result<()> {
+    let out = get_df()
+        .lazy()
+        .sort("sepal.width", Default::default())
+        .select([col("petal.length")])
+        .collect()?;
+
+    assert_eq!(out.get_column_names(), &["petal.length"]);
+    assert_eq!(
+        Vec::from(out.column("petal.length")?.f64()?),
+        &[
+            Some(1.4),
+            Some(1.5),
+            Some(1.3),
+            Some(1.4),
+            Some(1.4),
+            Some(1.4),
+            Some(1.7)
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sort_column_projected_away_after_sort() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => [3, 1, 2],
+        "c" => ["x", "y", "z"]
+    ]?;
+    let q = df.lazy().sort("b", Default::default()).select([col("a")]);
+
+    // the sort column is projected at the scan, and dropped right after sorting
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let above_sort = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::Projection { input, expr, .. }
+                if matches!(lp_arena.get(*input), ALogicalPlan::Sort { .. }) =>
+            {
+                Some(expr.clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+    let names = above_sort
+        .iter()
+        .flat_map(|node| aexpr_to_root_names(*node, &expr_arena))
+        .collect::<Vec<_>>();
+    assert_eq!(names, &[Arc::from("a")]);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["a"]);
+    assert_eq!(out.column("a")?, &Series::new("a", &[2, 3, 1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_projection_pd() -> Result<()> {
+    let q = scan_foods_csv()
+        .select([sum_exprs([col("calories"), col("fats_g"), col("sugars_g")]).alias("total")]);
+
+    // every column used in the fold is read
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let mut with_columns = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+            _ => None,
+        })
+        .unwrap();
+    with_columns.sort_unstable();
+    assert_eq!(with_columns, &["calories", "fats_g", "sugars_g"]);
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["total"]);
+    assert_eq!(out.column("total")?.dtype(), &DataType::Float64);
+    // vegetables,45,0.5,2
+    assert_eq!(out.column("total")?.f64()?.get(0), Some(47.5));
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_aggregations_pruned() -> Result<()> {
+    let q = scan_foods_csv()
+        .groupby_stable([col("category")])
+        .agg([
+            col("calories").sum(),
+            col("fats_g").sum().alias("fats_sum"),
+            col("sugars_g").max(),
+        ])
+        .select([col("category"), col("fats_sum")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let mut with_columns = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+            _ => None,
+        })
+        .unwrap();
+    with_columns.sort_unstable();
+    assert_eq!(with_columns, &["category", "fats_g"]);
+
+    let out = q.clone().collect()?;
+    let expected = q.with_projection_pushdown(false).collect()?;
+    assert!(out.frame_equal(&expected));
+    assert_eq!(out.get_column_names(), &["category", "fats_sum"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_renamed_aggregation_projection_pd() -> Result<()> {
+    let df = df![
+        "day" => [1, 1, 2, 2],
+        "temp" => [10, 12, 20, 22],
+        "rain" => [0.1, 0.0, 0.3, 0.2],
+        "wind" => [5, 6, 7, 8],
+    ]?;
+    let q = df
+        .lazy()
+        .groupby_stable([col("day")])
+        .agg([
+            col("temp").sum().alias("temp_sum"),
+            col("rain").mean().alias("rain_mean"),
+            col("wind").max(),
+        ])
+        .select([col("day"), (col("temp_sum") * lit(2)).alias("double_temp")]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let mut projected = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::DataFrameScan {
+                projection: Some(projection),
+                ..
+            } => Some(
+                projection
+                    .iter()
+                    .flat_map(|node| aexpr_to_root_names(*node, &expr_arena))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .unwrap();
+    projected.sort_unstable();
+    assert_eq!(projected, &[Arc::from("day"), Arc::from("temp")]);
+
+    let out = q.collect()?;
+    let expected = df![
+        "day" => [1, 2],
+        "double_temp" => [44, 84],
+    ]?;
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_wildcard_expansion_pd() -> Result<()> {
+    // the wildcard is expanded against the schema of the input, so projection pushdown only
+    // sees the explicitly named columns
+    let q = scan_foods_csv()
+        .select([col("calories"), col("fats_g")])
+        .select([col("*").sum()]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena).iter(lp).find_map(|(_, lp)| match lp {
+        ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+        _ => None,
+    });
+    assert_eq!(
+        with_columns,
+        Some(vec!["calories".to_string(), "fats_g".to_string()])
+    );
+
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["calories", "fats_g"]);
+    assert_eq!(out.height(), 1);
+
+    // wildcards with a rename pattern in an aggregation
+    let out = scan_foods_csv()
+        .groupby([col("category")])
+        .agg([col("*").max().suffix("_max")])
+        .collect()?;
+    assert_eq!(
+        out.get_column_names(),
+        &["category", "calories_max", "fats_g_max", "sugars_g_max"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_exclude_pd() -> Result<()> {
+    let q = scan_foods_csv().select([col("*").exclude(["category", "sugars_g"])]);
+
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena).iter(lp).find_map(|(_, lp)| match lp {
+        ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+        _ => None,
+    });
+    assert_eq!(
+        with_columns,
+        Some(vec!["calories".to_string(), "fats_g".to_string()])
+    );
+    assert_eq!(q.collect()?.get_column_names(), &["calories", "fats_g"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_with_column_pd() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => [10, 20, 30],
+        "d" => ["x", "y", "z"]
+    ]?;
+
+    let q = df.lazy().with_column((col("a") + col("b")).alias("c"));
+    assert_eq!(
+        q.schema().iter_names().collect::<Vec<_>>(),
+        &["a", "b", "d", "c"]
+    );
+    let out = q.clone().collect()?;
+    assert_eq!(out.get_column_names(), &["a", "b", "d", "c"]);
+    assert_eq!(out.column("c")?, &Series::new("c", &[11, 22, 33]));
+
+    // only the columns needed by `c` are projected at the scan
+    let q = q.select([col("c")]);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let projection = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::DataFrameScan { projection, .. } => projection.clone(),
+            _ => None,
+        })
+        .unwrap();
+    let mut names = projection
+        .iter()
+        .flat_map(|node| aexpr_to_root_names(*node, &expr_arena))
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, &[Arc::from("a"), Arc::from("b")]);
+    assert_eq!(q.collect()?.get_column_names(), &["c"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_with_column_pd() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => [10, 20, 30],
+        "d" => ["x", "y", "z"]
+    ]?;
+    let hstack_exprs = |q: LazyFrame| {
+        let (mut expr_arena, mut lp_arena) = get_arenas();
+        let lp = q.optimize(&mut lp_arena, &mut expr_arena).unwrap();
+        (&lp_arena).iter(lp).find_map(|(_, lp)| match lp {
+            ALogicalPlan::HStack { exprs, .. } => Some(exprs.len()),
+            _ => None,
+        })
+    };
+
+    // the new column is not selected, so the node is removed
+    let q = df
+        .clone()
+        .lazy()
+        .with_column((col("a") + col("b")).alias("c"))
+        .select([col("d")]);
+    assert_eq!(hstack_exprs(q.clone()), None);
+    let out = q.collect()?;
+    assert_eq!(out.get_column_names(), &["d"]);
+
+    // only the selected new column is computed, its inputs are preserved
+    let q = df
+        .lazy()
+        .with_columns([
+            (col("a") * lit(2)).alias("x"),
+            (col("b") * lit(2)).alias("y"),
+        ])
+        .select([col("x")]);
+    assert_eq!(hstack_exprs(q.clone()), Some(1));
+    let out = q.collect()?;
+    assert_eq!(out.column("x")?, &Series::new("x", &[2, 4, 6]));
+
+    Ok(())
+}
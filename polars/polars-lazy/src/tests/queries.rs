@@ -45,6 +45,22 @@ fn test_lazy_exec() {
     assert!(check.all())
 }
 
+#[test]
+fn test_lazy_filter_gt() -> Result<()> {
+    let df = get_df();
+    let q = df.clone().lazy().filter(col("sepal.width").gt(lit(3.0)));
+    // the predicate is pushed into the scan of the DataFrame
+    assert!(predicate_at_scan(q.clone()));
+    let out = q.collect()?;
+
+    let mask = df.column("sepal.width")?.f64()?.gt(3.0);
+    let expected = df.filter(&mask)?;
+    assert_eq!(out.height(), expected.height());
+    assert!(out.frame_equal(&expected));
+
+    Ok(())
+}
+
 #[test]
 fn test_lazy_alias() {
     let df = get_df();
@@ -77,6 +93,48 @@ fn test_lazy_melt() {
     assert_eq!(out.shape(), (7, 3));
 }
 
+#[test]
+fn test_lazy_melt_optimizations() -> Result<()> {
+    let df = df![
+        "id" => [1, 2],
+        "x" => [1, 2],
+        "y" => [3.0, 4.0]
+    ]?;
+    let args = MeltArgs {
+        id_vars: vec!["id".to_string()],
+        value_vars: vec!["x".to_string(), "y".to_string()],
+        variable_name: None,
+        value_name: None,
+    };
+
+    let q = df.clone().lazy().melt(args.clone());
+    let schema = q.schema();
+    assert_eq!(
+        schema.iter_names().collect::<Vec<_>>(),
+        &["id", "variable", "value"]
+    );
+    assert_eq!(schema.get("value"), Some(&DataType::Float64));
+
+    // the predicate on the melted values is not pushed below the melt
+    let out = q.filter(col("value").gt(lit(2.5))).collect()?;
+    assert_eq!(out.get_column_names(), &["id", "variable", "value"]);
+    assert_eq!(Vec::from(out.column("id")?.i32()?), &[Some(1), Some(2)]);
+
+    // without value columns, the columns that are not selected are melted
+    let args = MeltArgs {
+        value_vars: vec![],
+        ..args
+    };
+    let out = df
+        .lazy()
+        .melt(args)
+        .select([col("id"), col("value")])
+        .collect()?;
+    assert_eq!(out.shape(), (4, 2));
+
+    Ok(())
+}
+
 #[test]
 fn test_lazy_drop_nulls() {
     let df = df! {
@@ -637,6 +695,73 @@ fn test_lazy_fill_null() {
     assert_eq!(out.get_column_names(), vec!["a", "b"])
 }
 
+#[test]
+fn test_fill_null_strategies() -> Result<()> {
+    // two chunks, forward fill must carry the last value of the first chunk
+    let mut a = Series::new("a", &[Some(1), None]);
+    a.append(&Series::new("a", &[None, Some(4), None]))?;
+    assert_eq!(a.n_chunks(), 2);
+    let df = DataFrame::new(vec![a])?;
+
+    let out = df
+        .clone()
+        .lazy()
+        .select([
+            col("a").fill_null(lit(0)).alias("literal"),
+            col("a")
+                .fill_null_with_strategy(FillNullStrategy::Forward)
+                .alias("forward"),
+            col("a")
+                .fill_null_with_strategy(FillNullStrategy::Backward)
+                .alias("backward"),
+            col("a")
+                .fill_null_with_strategy(FillNullStrategy::Mean)
+                .alias("mean"),
+            col("a")
+                .fill_null_with_strategy(FillNullStrategy::Min)
+                .alias("min"),
+            col("a")
+                .fill_null_with_strategy(FillNullStrategy::Max)
+                .alias("max"),
+        ])
+        .collect()?;
+    for (name, expected) in [
+        ("literal", [Some(1), Some(0), Some(0), Some(4), Some(0)]),
+        ("forward", [Some(1), Some(1), Some(1), Some(4), Some(4)]),
+        ("backward", [Some(1), Some(4), Some(4), Some(4), None]),
+        ("mean", [Some(1), Some(2), Some(2), Some(4), Some(2)]),
+        ("min", [Some(1), Some(1), Some(1), Some(4), Some(1)]),
+        ("max", [Some(1), Some(4), Some(4), Some(4), Some(4)]),
+    ] {
+        assert_eq!(Vec::from(out.column(name)?.i32()?), &expected);
+    }
+
+    // a float fill value makes the column a float column
+    let out = df
+        .lazy()
+        .with_column(col("a").fill_null(lit(0.5)))
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.f64()?),
+        &[Some(1.0), Some(0.5), Some(0.5), Some(4.0), Some(0.5)]
+    );
+
+    // also if the column has no nulls
+    let df = df![
+        "a" => [1, 2, 3]
+    ]?;
+    let out = df
+        .lazy()
+        .with_column(col("a").fill_null(lit(0.5)))
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.f64()?),
+        &[Some(1.0), Some(2.0), Some(3.0)]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_lazy_double_projection() {
     let df = df! {
@@ -1205,6 +1330,41 @@ fn test_cross_join() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "semi_anti_join")]
+#[test]
+fn test_semi_anti_join() -> Result<()> {
+    let left = df![
+        "key" => [1, 2, 3, 4],
+        "v" => ["a", "b", "c", "d"],
+    ]?;
+    let right = df![
+        "key" => [2, 4, 5],
+        "v" => ["x", "y", "z"],
+    ]?;
+    let join = |right: &DataFrame, how: JoinType| {
+        left.clone()
+            .lazy()
+            .join(right.clone().lazy(), [col("key")], [col("key")], how)
+            .select([col("v"), col("key")])
+            .collect()
+    };
+
+    let out = join(&right, JoinType::Semi)?;
+    assert_eq!(out.get_column_names(), &["v", "key"]);
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(2), Some(4)]);
+    assert_eq!(Vec::from(out.column("v")?.utf8()?), &[Some("b"), Some("d")]);
+
+    let out = join(&right, JoinType::Anti)?;
+    assert_eq!(out.get_column_names(), &["v", "key"]);
+    assert_eq!(Vec::from(out.column("key")?.i32()?), &[Some(1), Some(3)]);
+
+    let empty = right.head(Some(0));
+    assert_eq!(join(&empty, JoinType::Semi)?.height(), 0);
+    assert_eq!(join(&empty, JoinType::Anti)?.height(), 4);
+
+    Ok(())
+}
+
 #[test]
 fn test_fold_wildcard() -> Result<()> {
     let df1 = df![
@@ -1278,9 +1438,20 @@ fn test_exclude() -> Result<()> {
     "c" => [1, 2, 3]
     ]?;
 
-    let out = df.lazy().select([col("*").exclude(&["b"])]).collect()?;
+    let out = df
+        .clone()
+        .lazy()
+        .select([col("*").exclude(&["b"])])
+        .collect()?;
 
     assert_eq!(out.get_column_names(), &["a", "c"]);
+
+    let err = df
+        .lazy()
+        .select([col("*").exclude(&["d"])])
+        .collect()
+        .unwrap_err();
+    assert!(matches!(err, PolarsError::NotFound(msg) if msg.contains("cannot exclude column d")));
     Ok(())
 }
 
@@ -1527,6 +1698,118 @@ fn test_when_then_schema() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_when_then_otherwise_literals() -> Result<()> {
+    let df = df![
+        "a" => [-1, 0, 2, 3]
+    ]?;
+    let q = df.lazy().select([when(col("a").gt(lit(0)))
+        .then(lit(1))
+        .otherwise(lit(0))
+        .alias("positive")]);
+    assert_eq!(q.schema().get("positive"), Some(&DataType::Int32));
+    let out = q.clone().collect()?;
+    assert_eq!(
+        Vec::from(out.column("positive")?.i32()?),
+        &[Some(0), Some(0), Some(1), Some(1)]
+    );
+
+    // the predicate must be boolean
+    let err = q
+        .select([when(col("positive")).then(lit(1)).otherwise(lit(0))])
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("must be boolean"));
+
+    // the output type of `map` defaults to the input type, so it is not checked
+    let out = df![
+        "a" => [-1, 0, 2, 3]
+    ]?
+    .lazy()
+    .select([
+        when(col("a").map(|s| Ok(s.gt(0)?.into_series()), GetOutput::same_type()))
+            .then(lit(1))
+            .otherwise(lit(0))
+            .alias("positive"),
+    ])
+    .collect()?;
+    assert_eq!(
+        Vec::from(out.column("positive")?.i32()?),
+        &[Some(0), Some(0), Some(1), Some(1)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_when_then_chain() -> Result<()> {
+    let df = df![
+        "g" => ["a", "a", "b", "b"],
+        "a" => [-1, 0, 2, 3]
+    ]?;
+    let sign = || {
+        when(col("a").gt(lit(0)))
+            .then(lit(1))
+            .when(col("a").lt(lit(0)))
+            .then(lit(-1.5))
+            .otherwise(lit(0))
+    };
+
+    // the branches are cast to their supertype
+    let q = df.clone().lazy().select([sign().alias("sign")]);
+    assert_eq!(q.schema().get("sign"), Some(&DataType::Float64));
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("sign")?.f64()?),
+        &[Some(-1.5), Some(0.0), Some(1.0), Some(1.0)]
+    );
+
+    let out = df
+        .lazy()
+        .groupby_stable([col("g")])
+        .agg([sign().sum().alias("sign")])
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("sign")?.f64()?),
+        &[Some(-1.5), Some(2.0)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lazy_cast() -> Result<()> {
+    let df = df![
+        "a" => [1, 2, 3],
+        "b" => ["1", "x", "3"]
+    ]?;
+
+    let q = df.clone().lazy().select([col("a").cast(DataType::Float64)]);
+    assert_eq!(q.schema().get("a"), Some(&DataType::Float64));
+    let out = q.collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.f64()?),
+        &[Some(1.0), Some(2.0), Some(3.0)]
+    );
+
+    let out = df
+        .clone()
+        .lazy()
+        .filter(col("a").cast(DataType::Float64).gt(lit(1.5)))
+        .collect()?;
+    assert_eq!(out.height(), 2);
+
+    // a strict cast that fails is an error that names the column
+    let err = df
+        .lazy()
+        .select([col("b").strict_cast(DataType::Int32)])
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot cast column \"b\""));
+
+    Ok(())
+}
+
 #[test]
 fn test_singleton_broadcast() -> Result<()> {
     let df = fruits_cars();
@@ -1563,6 +1846,77 @@ fn test_sort_by_suffix() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_sort_nulls_last() -> Result<()> {
+    let df = df![
+        "a" => [Some(2), None, Some(1), Some(3), None],
+        "b" => [1, 2, 3, 4, 5],
+    ]?;
+    let sorted = |reverse: bool, nulls_last: bool| -> Result<Vec<Option<i32>>> {
+        let out = df
+            .clone()
+            .lazy()
+            .sort_by_exprs_with_nulls_last([col("a")], vec![reverse], vec![nulls_last])
+            .collect()?;
+        Ok(Vec::from(out.column("a")?.i32()?))
+    };
+
+    assert_eq!(
+        sorted(false, false)?,
+        &[None, None, Some(1), Some(2), Some(3)]
+    );
+    assert_eq!(
+        sorted(false, true)?,
+        &[Some(1), Some(2), Some(3), None, None]
+    );
+    assert_eq!(
+        sorted(true, false)?,
+        &[None, None, Some(3), Some(2), Some(1)]
+    );
+    assert_eq!(
+        sorted(true, true)?,
+        &[Some(3), Some(2), Some(1), None, None]
+    );
+
+    // the nulls of each key are placed within the groups of the previous keys
+    let df = df![
+        "a" => [1, 1, 1, 2, 2],
+        "b" => [Some(1), None, Some(2), None, Some(3)],
+    ]?;
+    let out = df
+        .clone()
+        .lazy()
+        .sort_by_exprs_with_nulls_last([col("a"), col("b")], vec![true, false], vec![false, true])
+        .collect()?;
+    let expected = df![
+        "a" => [2, 2, 1, 1, 1],
+        "b" => [Some(3), None, Some(1), Some(2), None],
+    ]?;
+    assert!(out.frame_equal_missing(&expected));
+
+    // a single flag is used for all keys, other lengths are an error
+    let out = df
+        .clone()
+        .lazy()
+        .sort_by_exprs_with_nulls_last([col("a"), col("b")], vec![true], vec![false])
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.i32()?),
+        &[Some(2), Some(2), Some(1), Some(1), Some(1)]
+    );
+    for (reverse, nulls_last) in [(vec![], vec![false]), (vec![true], vec![true, false, true])] {
+        let err = df
+            .clone()
+            .lazy()
+            .sort_by_exprs_with_nulls_last([col("a"), col("b")], reverse, nulls_last)
+            .collect()
+            .unwrap_err();
+        assert!(err.to_string().contains("a sort by 2 keys got"));
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_list_in_select_context() -> Result<()> {
     let s = Series::new("a", &[1, 2, 3]);
@@ -1767,6 +2121,53 @@ fn test_binary_expr() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_binary_arithmetic_promotion() -> Result<()> {
+    let df = df![
+        "a" => [Some(1i32), Some(2), None],
+        "b" => [0.5f64, 0.0, 1.0],
+    ]?;
+
+    let out = df
+        .lazy()
+        .select([
+            (col("a") + col("b")).alias("sum"),
+            (col("a") * col("b")).alias("product"),
+            (col("a") / col("b")).alias("quotient"),
+            col("a").gt(col("b")).alias("gt"),
+        ])
+        .collect()?;
+    assert_eq!(
+        out.dtypes(),
+        &[
+            DataType::Float64,
+            DataType::Float64,
+            DataType::Float64,
+            DataType::Boolean
+        ]
+    );
+    // nulls propagate
+    assert_eq!(
+        Vec::from(out.column("sum")?.f64()?),
+        &[Some(1.5), Some(2.0), None]
+    );
+    assert_eq!(
+        Vec::from(out.column("product")?.f64()?),
+        &[Some(0.5), Some(0.0), None]
+    );
+    // a float division by zero is infinite, not null
+    assert_eq!(
+        Vec::from(out.column("quotient")?.f64()?),
+        &[Some(2.0), Some(f64::INFINITY), None]
+    );
+    assert_eq!(
+        Vec::from(out.column("gt")?.bool()?),
+        &[Some(true), Some(true), None]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_drop_and_select() -> Result<()> {
     let df = fruits_cars();
@@ -1785,7 +2186,7 @@ fn test_drop_and_select() -> Result<()> {
     assert_eq!(out.get_column_names(), &["fruits"]);
 
     let out = scan_foods_csv()
-        .drop_columns(["calories", "sugar_g"])
+        .drop_columns(["calories", "sugars_g"])
         .select([col("category")])
         .collect()?;
 
@@ -1793,6 +2194,69 @@ fn test_drop_and_select() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_drop_columns() -> Result<()> {
+    let out = fruits_cars().lazy().drop_columns(["A", "cars"]).collect()?;
+    assert_eq!(out.get_column_names(), &["fruits", "B"]);
+
+    // the remaining columns are pushed down to the scan
+    let q = scan_foods_csv().drop_columns(["calories"]);
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.optimize(&mut lp_arena, &mut expr_arena)?;
+    let with_columns = (&lp_arena).iter(lp).find_map(|(_, lp)| match lp {
+        ALogicalPlan::CsvScan { options, .. } => options.with_columns.clone(),
+        _ => None,
+    });
+    assert_eq!(
+        with_columns,
+        Some(vec![
+            "category".to_string(),
+            "fats_g".to_string(),
+            "sugars_g".to_string()
+        ])
+    );
+
+    let out = fruits_cars().lazy().drop_columns(["not_there"]).collect();
+    assert!(out.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_join_on_missing_column() -> Result<()> {
+    let df = fruits_cars();
+
+    let err = df
+        .clone()
+        .lazy()
+        .inner_join(df.clone().lazy(), col("not_there"), col("A"))
+        .collect()
+        .unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("not_there") && msg.contains("left"));
+    assert!(msg.contains("fruits") && msg.contains("cars"));
+
+    let out = df
+        .clone()
+        .lazy()
+        .inner_join(df.lazy(), col("A"), col("not_there"))
+        .collect();
+    assert!(matches!(out, Err(PolarsError::NotFound(msg)) if msg.contains("right")));
+
+    Ok(())
+}
+
+#[test]
+fn test_unresolvable_column_is_an_error() {
+    // the selection is not validated by the builder; the optimizer finds the missing column
+    let out = fruits_cars()
+        .lazy()
+        .filter(col("not_there").gt(lit(1)))
+        .select([col("A")])
+        .collect();
+    assert!(matches!(out, Err(PolarsError::NotFound(name)) if name == "not_there"));
+}
+
 #[test]
 fn test_groupby_on_lists() -> Result<()> {
     let s0 = Series::new("", [1i32, 2, 3]);
@@ -1956,3 +2420,165 @@ fn test_is_in() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_expr_eq_and_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |e: &Expr| {
+        let mut hasher = DefaultHasher::new();
+        e.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let a = col("a").gt(lit(1));
+    let b = col("a").gt(lit(1));
+    assert!(a == b);
+    assert_eq!(hash(&a), hash(&b));
+
+    assert!(a != col("a").gt(lit(2)));
+    assert!(a != col("b").gt(lit(1)));
+    assert_ne!(hash(&a), hash(&col("b").gt(lit(1))));
+    assert_ne!(hash(&a), hash(&col("a").lt(lit(1))));
+}
+
+#[test]
+fn test_unique() -> Result<()> {
+    let df = df![
+        "a" => [1, 1, 2, 1],
+        "b" => [1, 1, 2, 2]
+    ]?;
+
+    let out = df
+        .clone()
+        .lazy()
+        .unique_stable(None, UniqueKeepStrategy::First)
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("a")?.i32()?),
+        &[Some(1), Some(2), Some(1)]
+    );
+    assert_eq!(
+        Vec::from(out.column("b")?.i32()?),
+        &[Some(1), Some(2), Some(2)]
+    );
+
+    // the subset is projected, even if only the other column is selected
+    let out = df
+        .lazy()
+        .unique_stable(Some(vec!["a".into()]), UniqueKeepStrategy::First)
+        .select([col("b")])
+        .collect()?;
+    assert_eq!(Vec::from(out.column("b")?.i32()?), &[Some(1), Some(2)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_join_and_groupby_schema() -> Result<()> {
+    let right = fruits_cars()
+        .lazy()
+        .select([col("A"), col("B"), col("cars")]);
+    let q = fruits_cars().lazy().inner_join(right, col("A"), col("A"));
+    let schema = q.schema();
+    assert_eq!(
+        schema.iter_names().collect::<Vec<_>>(),
+        &["A", "fruits", "B", "cars", "B_right", "cars_right"]
+    );
+    assert_eq!(*schema, q.collect()?.schema());
+
+    let q = fruits_cars().lazy().groupby([col("fruits")]).agg([
+        col("A").sum(),
+        col("B").mean(),
+        col("cars").count().alias("n"),
+    ]);
+    let schema = q.schema();
+    assert_eq!(
+        schema.iter_names().collect::<Vec<_>>(),
+        &["fruits", "A", "B", "n"]
+    );
+    assert_eq!(schema.get("fruits"), Some(&DataType::Utf8));
+    assert_eq!(schema.get("A"), Some(&DataType::Int32));
+    assert_eq!(schema.get("B"), Some(&DataType::Float64));
+    assert_eq!(schema.get("n"), Some(&IDX_DTYPE));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "strings")]
+fn test_str_predicates() -> Result<()> {
+    let df = fruits_cars();
+    let height = |predicate: Expr| -> Result<usize> {
+        let q = df.clone().lazy().filter(predicate);
+        assert!(predicate_at_scan(q.clone()));
+        Ok(q.collect()?.height())
+    };
+
+    assert_eq!(height(col("fruits").str().contains("nan"))?, 3);
+    assert_eq!(height(col("fruits").str().contains("^a"))?, 2);
+    assert_eq!(height(col("fruits").str().starts_with("app"))?, 2);
+    assert_eq!(height(col("cars").str().ends_with("le"))?, 4);
+
+    let out = df
+        .clone()
+        .lazy()
+        .select([col("cars").str().starts_with("au")])
+        .collect()?;
+    assert_eq!(
+        Vec::from(out.column("cars")?.bool()?),
+        &[
+            Some(false),
+            Some(true),
+            Some(false),
+            Some(false),
+            Some(false)
+        ]
+    );
+
+    // the input must be a string column
+    let err = df
+        .lazy()
+        .select([col("A").str().contains("1")])
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("expects a Utf8 column"));
+
+    Ok(())
+}
+
+#[test]
+fn test_partitioned_mean_keeps_rows() -> Result<()> {
+    let q = fruits_cars()
+        .lazy()
+        .select([col("A").mean().over([col("fruits")]).alias("mean_A")]);
+
+    // both the aggregated and the partition column are projected
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let lp = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let projection = (&lp_arena)
+        .iter(lp)
+        .find_map(|(_, lp)| match lp {
+            ALogicalPlan::DataFrameScan { projection, .. } => projection.clone(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(projection.len(), 2);
+
+    let out = q.collect()?;
+    assert_eq!(out.shape(), (5, 1));
+    let banana = 8.0 / 3.0;
+    assert_eq!(
+        Vec::from(out.column("mean_A")?.f64()?),
+        &[
+            Some(banana),
+            Some(banana),
+            Some(3.5),
+            Some(3.5),
+            Some(banana)
+        ]
+    );
+
+    Ok(())
+}
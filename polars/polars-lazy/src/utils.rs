@@ -1,3 +1,4 @@
+use crate::logical_plan::conversion::{node_to_expr, to_aexpr};
 use crate::logical_plan::iterator::{ArenaExprIter, ArenaLpIter};
 use crate::logical_plan::Context;
 use crate::prelude::*;
@@ -69,6 +70,35 @@ where
     arena.iter(current_node).any(|(_node, e)| matches(e))
 }
 
+/// Check if an expression is evaluated row by row, so that the value of a row does not depend on
+/// the other rows. Such an expression gives the same result on a filtered or aggregated frame.
+pub(crate) fn aexpr_is_elementwise(current_node: Node, arena: &Arena<AExpr>) -> bool {
+    let not_elementwise = |e: &AExpr| match e {
+        // a series that is not a singleton is compared with the column element wise
+        AExpr::Literal(LiteralValue::Series(s)) => s.len() > 1,
+        AExpr::Literal(LiteralValue::Range { .. }) => true,
+        AExpr::Column(_)
+        | AExpr::Alias(_, _)
+        | AExpr::Literal(_)
+        | AExpr::BinaryExpr { .. }
+        | AExpr::Not(_)
+        | AExpr::IsNull(_)
+        | AExpr::IsNotNull(_)
+        | AExpr::Cast { .. }
+        | AExpr::Ternary { .. } => false,
+        AExpr::Function {
+            options:
+                FunctionOptions {
+                    collect_groups: ApplyOptions::ApplyFlat,
+                    ..
+                },
+            ..
+        } => false,
+        _ => true,
+    };
+    !has_aexpr(current_node, arena, not_elementwise)
+}
+
 pub(crate) fn has_window_aexpr(current_node: Node, arena: &Arena<AExpr>) -> bool {
     has_aexpr(current_node, arena, |e| matches!(e, AExpr::Window { .. }))
 }
@@ -192,25 +222,24 @@ pub(crate) fn rename_aexpr_root_names(node: Node, arena: &mut Arena<AExpr>, new_
     }
 }
 
-/// Rename the root of the expression from `current` to `new` and assign to new node in arena.
-/// Returns `Node` on first sucessful rename.
+/// Copy the expression tree with the root columns named `current` renamed to `new_name` and
+/// assign it to new nodes in the arena.
 pub(crate) fn aexpr_assign_renamed_root(
     node: Node,
     arena: &mut Arena<AExpr>,
     current: &str,
     new_name: &str,
 ) -> Node {
-    let roots = aexpr_to_root_nodes(node, arena);
-
-    for node in roots {
-        match arena.get(node) {
-            AExpr::Column(name) if &**name == current => {
-                return arena.add(AExpr::Column(Arc::from(new_name)))
+    let mut expr = node_to_expr(node, arena);
+    expr.mutate().apply(|e| {
+        if let Expr::Column(name) = e {
+            if &**name == current {
+                *e = Expr::Column(Arc::from(new_name));
             }
-            _ => {}
         }
-    }
-    panic!("should be a root column that is renamed");
+        true
+    });
+    to_aexpr(expr, arena)
 }
 
 /// Get all root column expressions in the expression tree.